@@ -5,13 +5,26 @@ use std::{
 };
 
 use anyhow::{ensure, Result};
-use clap::Parser;
+use clap::{parser::ValueSource, ArgMatches, CommandFactory, FromArgMatches, Parser};
 use ratatui::style::Color as TuiColor;
+use serde::{de, Deserialize, Deserializer};
 
-use crate::interface::ui::customizations::BorderStyle;
+use crate::{
+    interface::{
+        matcher::{MatchMode, Matcher, SearchCase, SearchField},
+        ui::customizations::BorderStyle,
+    },
+    parse::{
+        columns::{Columns, Fields},
+        hotkey::{DuplicateKeys, KeyCase, KeyPolicy},
+        Header,
+        InputFormat,
+    },
+};
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Cli {
     #[arg(long, default_value_t = Color(TuiColor::Black))]
     pub color_selected_fg: Color,
@@ -31,8 +44,124 @@ pub struct Cli {
     #[arg(long, short, value_enum, default_value_t = BorderStyle::Thick)]
     pub border_style: BorderStyle,
 
+    #[arg(long, value_enum, default_value_t = KeyCase::Sensitive)]
+    pub key_case: KeyCase,
+
+    #[arg(long, value_enum, default_value_t = DuplicateKeys::First)]
+    pub duplicate_keys: DuplicateKeys,
+
+    #[arg(long)]
+    pub auto_keys: bool,
+
+    #[arg(long = "match", value_enum, default_value_t = MatchMode::Fuzzy)]
+    pub match_mode: MatchMode,
+
+    #[arg(long, value_enum, default_value_t = SearchCase::Smart)]
+    pub search_case: SearchCase,
+
+    #[arg(long, value_enum, value_delimiter = ',', default_values_t = [SearchField::Display])]
+    pub search_in: Vec<SearchField>,
+
+    #[arg(long, short)]
+    pub title: Option<String>,
+
+    #[arg(long)]
+    pub prompt: Option<String>,
+
     #[arg(long, short)]
-    pub options_file: Option<PathBuf>,
+    pub options_file: Vec<PathBuf>,
+
+    #[arg(long, short, conflicts_with_all = ["options_file", "input_format", "plain"])]
+    pub menu: Option<PathBuf>,
+
+    #[arg(long, conflicts_with_all = ["options_file", "menu"])]
+    pub source_command: Option<String>,
+
+    #[arg(long, value_enum, default_value_t = InputFormat::Lines)]
+    pub input_format: InputFormat,
+
+    #[arg(long, conflicts_with = "input_format")]
+    pub plain: bool,
+
+    #[arg(long, conflicts_with = "menu")]
+    pub read0: bool,
+
+    #[arg(long)]
+    pub print0: bool,
+
+    #[arg(long)]
+    pub ansi: bool,
+
+    #[arg(long, short, conflicts_with_all = ["menu", "input_format", "plain"])]
+    pub delimiter: Option<String>,
+
+    #[arg(long, conflicts_with_all = ["menu", "input_format", "plain"])]
+    pub with_nth: Option<Fields>,
+
+    #[arg(long, conflicts_with_all = ["menu", "input_format", "plain"])]
+    pub output_nth: Option<Fields>,
+
+    #[arg(long)]
+    pub check: bool,
+
+    #[arg(value_name = "OPTION", conflicts_with = "source_command")]
+    pub options: Vec<String>,
+}
+impl Cli {
+    pub fn parse_with_matches() -> Result<(Self, ArgMatches)> {
+        let matches = Self::command().get_matches();
+        let arguments = Self::from_arg_matches(&matches)?;
+        Ok((arguments, matches))
+    }
+    pub fn apply_header(&mut self, header: Header, matches: &ArgMatches) {
+        let is_default =
+            |id: &str| !matches!(matches.value_source(id), Some(ValueSource::CommandLine));
+        let apply = |id: &str, target: &mut Color, value: Option<Color>| {
+            if let Some(value) = value.filter(|_| is_default(id)) {
+                *target = value;
+            }
+        };
+
+        apply("color_selected_fg", &mut self.color_selected_fg, header.color_selected_fg);
+        apply("color_selected_bg", &mut self.color_selected_bg, header.color_selected_bg);
+        apply("color_key_fg", &mut self.color_key_fg, header.color_key_fg);
+        apply("color_key_bg", &mut self.color_key_bg, header.color_key_bg);
+        apply("color_border", &mut self.color_border, header.color_border);
+
+        if let Some(border_style) = header.border_style.filter(|_| is_default("border_style")) {
+            self.border_style = border_style;
+        }
+        if self.title.is_none() {
+            self.title = header.title;
+        }
+        if self.prompt.is_none() {
+            self.prompt = header.prompt;
+        }
+    }
+    pub const fn input_format(&self) -> InputFormat {
+        if self.plain {
+            InputFormat::Plain
+        }
+        else {
+            self.input_format
+        }
+    }
+    pub fn columns(&self) -> Option<Columns> {
+        let Self { delimiter, with_nth, output_nth, .. } = self;
+        let tabular = delimiter.is_some() || with_nth.is_some() || output_nth.is_some();
+        tabular.then(|| Columns {
+            delimiter: delimiter.clone(),
+            display: with_nth.clone(),
+            output: output_nth.clone(),
+        })
+    }
+    pub const fn key_policy(&self) -> KeyPolicy {
+        KeyPolicy { case: self.key_case, duplicates: self.duplicate_keys, auto: self.auto_keys }
+    }
+    pub fn matcher(&self) -> Matcher {
+        let fields = self.search_in.iter().copied().collect();
+        Matcher { mode: self.match_mode, case: self.search_case, fields }
+    }
 }
 
 #[derive(Clone)]
@@ -55,6 +184,12 @@ impl Color {
     }
 }
 
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(de::Error::custom)
+    }
+}
 impl From<Color> for TuiColor {
     fn from(value: Color) -> Self {
         value.0
@@ -1,13 +1,16 @@
 use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub up_key: char,
     pub down_key: char,
     pub search_key: char,
+    pub reload_key: char,
+    pub match_key: char,
 }
 
 impl Default for Config {
     fn default() -> Self {
-        Self { up_key: 'j', down_key: 'k', search_key: 's' }
+        Self { up_key: 'j', down_key: 'k', search_key: 's', reload_key: 'r', match_key: 'f' }
     }
 }
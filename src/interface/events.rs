@@ -1,51 +1,53 @@
-use std::io::{stdout, Write};
+use std::{
+    io::{stdout, Write},
+    time::Duration,
+};
 
 use anyhow::{Context, Result};
 use crossterm::event::{self, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
-use event::{read, Event, KeyCode, KeyModifiers};
+use event::{poll, read, Event, KeyCode, KeyModifiers};
 
 use super::ui::InputMode;
 use crate::{
     interface::{ui::Vector, Terminal, Ui},
-    parse::MenuOption,
+    parse::hotkey::Key,
     Config,
 };
 
+const LOADING_POLL: Duration = Duration::from_millis(50);
+
 pub enum Choice {
-    Chosen(usize),
+    Chosen(String),
     None,
 }
 
 impl Choice {
-    pub fn print(&self, options: &[MenuOption]) -> Result<()> {
-        if let Self::Chosen(index) = self {
-            let mut stdout = stdout().lock();
-            let chosen = &options[*index].output;
-            writeln!(stdout, "{chosen}")?;
+    pub fn print(&self, print0: bool) -> Result<()> {
+        if let Self::Chosen(output) = self {
+            let terminator = if print0 { '\0' } else { '\n' };
+            write!(stdout().lock(), "{output}{terminator}")?;
         }
         Ok(())
     }
 }
-pub fn event_loop(
-    terminal: &mut Terminal,
-    mut ui: Ui,
-    options: &[MenuOption],
-    config: &Config,
-) -> Result<Choice> {
+pub fn event_loop(terminal: &mut Terminal, mut ui: Ui, config: &Config) -> Result<Choice> {
     let mut choice = None;
     while choice.is_none() {
+        ui.receive()?;
         terminal
             .draw(|frame| ui.render(frame))
             .context("Drawing the rendered inteface to the terminal failed.")?;
-        choice = handle_event(&mut ui, options, config)
-            .context("Handling the incoming event failed.")?;
+        if ui.is_loading() && !poll(LOADING_POLL).context("Polling for events failed.")? {
+            continue;
+        }
+        choice = handle_event(&mut ui, config).context("Handling the incoming event failed.")?;
     }
     Ok(choice.unwrap())
 }
-fn handle_event(ui: &mut Ui, options: &[MenuOption], config: &Config) -> Result<Option<Choice>> {
+fn handle_event(ui: &mut Ui, config: &Config) -> Result<Option<Choice>> {
     match read().context("Reading event from backend failed.")? {
-        Event::Key(key) => Ok(handle_key(key, ui, options, config)),
-        Event::Mouse(mouse) => Ok(handle_mouse(mouse, ui).map(Choice::Chosen)),
+        Event::Key(key) => Ok(handle_key(key, ui, config)),
+        Event::Mouse(mouse) => Ok(handle_mouse(mouse, ui).and_then(|index| choose(ui, index))),
         _ => Ok(None),
     }
 }
@@ -63,40 +65,54 @@ fn handle_mouse(mouse: MouseEvent, ui: &mut Ui) -> Option<usize> {
     }
     None
 }
-fn handle_key(
-    key: KeyEvent,
-    ui: &mut Ui,
-    options: &[MenuOption],
-    config: &Config,
-) -> Option<Choice> {
-    match key.modifiers {
-        KeyModifiers::NONE => match key.code {
+fn handle_key(key: KeyEvent, ui: &mut Ui, config: &Config) -> Option<Choice> {
+    let hotkey = Key::from(key);
+    if matches!(ui.input_mode, InputMode::Selecting) && ui.list.is_bound(hotkey) {
+        return ui.list.press(hotkey).and_then(|index| choose(ui, index));
+    }
+
+    match hotkey.modifiers {
+        KeyModifiers::NONE => match hotkey.code {
             KeyCode::Down => ui.list.state.next(),
             KeyCode::Up => ui.list.state.previous(),
+            KeyCode::Left if matches!(ui.input_mode, InputMode::Selecting) && ui.ascend() => {},
             KeyCode::Left => ui.list.state.unselect(),
             KeyCode::Char(character) => match ui.input_mode {
                 InputMode::Searching => ui.append_query(character),
-                InputMode::Selecting => return map_char(character, options).map(Choice::Chosen),
+                InputMode::Selecting => ui.list.pending.clear(),
+            },
+            KeyCode::Backspace => match ui.input_mode {
+                InputMode::Searching => ui.pop_query(),
+                InputMode::Selecting if ui.list.pending.is_empty() => {
+                    ui.ascend();
+                },
+                InputMode::Selecting => ui.list.pending.pop(),
             },
-            KeyCode::Backspace => {
-                if matches!(ui.input_mode, InputMode::Searching) {
-                    ui.pop_query();
-                }
+            KeyCode::Enter => {
+                let index = ui.list.state.selected().or_else(|| ui.list.state.first());
+                return index.and_then(|index| choose(ui, index));
             },
-            KeyCode::Enter => return Some(Choice::Chosen(ui.list.state.selected().unwrap_or(0))),
+            KeyCode::Esc if !ui.list.pending.is_empty() => ui.list.pending.clear(),
             KeyCode::Esc => return Some(Choice::None),
             _ => {},
         },
-        KeyModifiers::CONTROL => match key.code {
+        KeyModifiers::CONTROL => match hotkey.code {
             KeyCode::Char(c) if c == config.down_key => ui.list.state.previous(),
             KeyCode::Char(c) if c == config.up_key => ui.list.state.next(),
             KeyCode::Char(c) if c == config.search_key => ui.input_mode.switch(),
+            KeyCode::Char(c) if c == config.reload_key => ui.reload(),
+            KeyCode::Char(c) if c == config.match_key => ui.cycle_matcher(),
             _ => (),
         },
         _ => {},
     }
     None
 }
-fn map_char(key: char, options: &[MenuOption]) -> Option<usize> {
-    options.iter().position(|option| option.key == key)
+fn choose(ui: &mut Ui, index: usize) -> Option<Choice> {
+    if ui.descend(index) {
+        None
+    }
+    else {
+        Some(Choice::Chosen(ui.list.option(index).output.to_string()))
+    }
 }
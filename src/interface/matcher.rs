@@ -0,0 +1,439 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    mem,
+};
+
+use clap::ValueEnum;
+use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
+use regex::{Regex, RegexBuilder};
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum MatchMode {
+    Exact,
+    Prefix,
+    Substring,
+    Regex,
+    Fuzzy,
+}
+impl MatchMode {
+    const fn cycled(self) -> Self {
+        match self {
+            Self::Exact => Self::Prefix,
+            Self::Prefix => Self::Substring,
+            Self::Substring => Self::Regex,
+            Self::Regex => Self::Fuzzy,
+            Self::Fuzzy => Self::Exact,
+        }
+    }
+}
+impl Display for MatchMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Exact => "exact",
+            Self::Prefix => "prefix",
+            Self::Substring => "substring",
+            Self::Regex => "regex",
+            Self::Fuzzy => "fuzzy",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SearchCase {
+    Smart,
+    Sensitive,
+    Insensitive,
+}
+impl SearchCase {
+    fn ignores(self, term: &str) -> bool {
+        match self {
+            Self::Smart => !term.chars().any(char::is_uppercase),
+            Self::Sensitive => false,
+            Self::Insensitive => true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SearchField {
+    Display,
+    Output,
+    Key,
+}
+impl SearchField {
+    const ALL: [Self; 3] = [Self::Display, Self::Output, Self::Key];
+
+    fn strip(word: &str) -> Option<(Self, &str)> {
+        let (name, word) = word.split_once(':')?;
+        let field = match name {
+            "display" | "disp" => Self::Display,
+            "output" | "out" => Self::Output,
+            "key" => Self::Key,
+            _ => return None,
+        };
+        Some((field, word))
+    }
+}
+impl Display for SearchField {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Display => "display",
+            Self::Output => "output",
+            Self::Key => "key",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct SearchFields {
+    display: bool,
+    output: bool,
+    key: bool,
+}
+impl SearchFields {
+    const fn contains(self, field: SearchField) -> bool {
+        match field {
+            SearchField::Display => self.display,
+            SearchField::Output => self.output,
+            SearchField::Key => self.key,
+        }
+    }
+    fn iter(self) -> impl Iterator<Item = SearchField> {
+        SearchField::ALL.into_iter().filter(move |&field| self.contains(field))
+    }
+}
+impl FromIterator<SearchField> for SearchFields {
+    fn from_iter<I: IntoIterator<Item = SearchField>>(fields: I) -> Self {
+        let mut set = Self::default();
+        for field in fields {
+            match field {
+                SearchField::Display => set.display = true,
+                SearchField::Output => set.output = true,
+                SearchField::Key => set.key = true,
+            }
+        }
+        set
+    }
+}
+
+pub struct Subject<'s> {
+    pub display: &'s str,
+    pub output: &'s str,
+    pub key: &'s str,
+}
+impl<'s> Subject<'s> {
+    pub const fn field(&self, field: SearchField) -> &'s str {
+        match field {
+            SearchField::Display => self.display,
+            SearchField::Output => self.output,
+            SearchField::Key => self.key,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Matcher {
+    pub mode: MatchMode,
+    pub case: SearchCase,
+    pub fields: SearchFields,
+}
+impl Matcher {
+    pub const fn cycled(self) -> Self {
+        Self { mode: self.mode.cycled(), ..self }
+    }
+    pub fn pattern(self, query: &str) -> Pattern {
+        if self.mode == MatchMode::Regex {
+            let term = Term::new(self.mode, query, self.case.ignores(query));
+            let clause = Clause { term, negated: false, fields: self.fields };
+            return Pattern { groups: vec![vec![clause]] };
+        }
+
+        let mut groups: Vec<Vec<Clause>> = Vec::new();
+        let mut alternative = false;
+        for word in words(query) {
+            if word == ALTERNATIVE {
+                alternative = !groups.is_empty();
+                continue;
+            }
+            let Some(clause) = self.clause(&word)
+            else {
+                continue;
+            };
+            match groups.last_mut() {
+                Some(group) if alternative => group.push(clause),
+                _ => groups.push(vec![clause]),
+            }
+            alternative = false;
+        }
+        Pattern { groups }
+    }
+    fn clause(self, word: &str) -> Option<Clause> {
+        let (negated, word) = word.strip_prefix('!').map_or((false, word), |word| (true, word));
+        let (fields, word) = SearchField::strip(word)
+            .map_or((self.fields, word), |(field, word)| (SearchFields::from_iter([field]), word));
+        let (quoted, word) = word.strip_prefix('\'').map_or((false, word), |word| (true, word));
+        let (start, word) = word.strip_prefix('^').map_or((false, word), |word| (true, word));
+        let (end, word) = word.strip_suffix('$').map_or((false, word), |word| (true, word));
+        if word.is_empty() {
+            return None;
+        }
+
+        let ignore_case = self.case.ignores(word);
+        let anchor = match (start, end) {
+            (true, true) => Anchor::Whole,
+            (true, false) => Anchor::Start,
+            (false, true) => Anchor::End,
+            (false, false) if quoted || negated => Anchor::Anywhere,
+            (false, false) => {
+                let term = Term::new(self.mode, word, ignore_case);
+                return Some(Clause { term, negated, fields });
+            },
+        };
+        let term = Term::Literal { anchor, term: word.chars().collect(), ignore_case };
+        Some(Clause { term, negated, fields })
+    }
+}
+
+const ALTERNATIVE: &str = "|";
+
+fn words(query: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut chars = query.chars().peekable();
+    while let Some(character) = chars.next() {
+        match character {
+            '\\' => word.push(chars.next_if_eq(&' ').unwrap_or('\\')),
+            character if character.is_whitespace() => {
+                if !word.is_empty() {
+                    words.push(mem::take(&mut word));
+                }
+            },
+            character => word.push(character),
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+pub struct Pattern {
+    groups: Vec<Vec<Clause>>,
+}
+impl Pattern {
+    pub fn score(&self, subject: &Subject) -> Option<i64> {
+        let best =
+            |group: &Vec<Clause>| group.iter().filter_map(|clause| clause.score(subject)).max();
+        self.groups.iter().map(best).sum()
+    }
+    pub fn indices(&self, display: &str) -> Vec<usize> {
+        let mut indices = self
+            .clauses()
+            .filter(|clause| clause.fields.contains(SearchField::Display))
+            .flat_map(|clause| clause.term.indices(display))
+            .collect::<Vec<_>>();
+        indices.sort_unstable();
+        indices.dedup();
+        indices
+    }
+    pub fn hidden_matches(&self, subject: &Subject) -> Vec<SearchField> {
+        let mut hidden = Vec::new();
+        let matches = |clause: &Clause, field| {
+            clause.fields.contains(field) && clause.term.score(subject.field(field)).is_some()
+        };
+        for clause in self.clauses().filter(|clause| !matches(clause, SearchField::Display)) {
+            for field in [SearchField::Output, SearchField::Key] {
+                if matches(clause, field) && !hidden.contains(&field) {
+                    hidden.push(field);
+                }
+            }
+        }
+        hidden
+    }
+    fn clauses(&self) -> impl Iterator<Item = &Clause> {
+        self.groups.iter().flatten().filter(|clause| !clause.negated)
+    }
+}
+
+struct Clause {
+    term: Term,
+    negated: bool,
+    fields: SearchFields,
+}
+impl Clause {
+    fn score(&self, subject: &Subject) -> Option<i64> {
+        let scores = self.fields.iter().filter_map(|field| self.term.score(subject.field(field)));
+        let score = scores.max();
+        if self.negated {
+            score.is_none().then_some(0)
+        }
+        else {
+            score
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Anchor {
+    Whole,
+    Start,
+    End,
+    Anywhere,
+}
+
+enum Term {
+    Fuzzy(Box<SkimMatcherV2>, String),
+    Literal { anchor: Anchor, term: Vec<char>, ignore_case: bool },
+    Regex(Regex),
+    Invalid,
+}
+impl Term {
+    fn new(mode: MatchMode, term: &str, ignore_case: bool) -> Self {
+        let literal = |anchor| Self::Literal { anchor, term: term.chars().collect(), ignore_case };
+        match mode {
+            MatchMode::Fuzzy => {
+                let matcher = SkimMatcherV2::default();
+                let matcher =
+                    if ignore_case { matcher.ignore_case() } else { matcher.respect_case() };
+                Self::Fuzzy(Box::new(matcher), String::from(term))
+            },
+            MatchMode::Regex => {
+                let regex = RegexBuilder::new(term).case_insensitive(ignore_case).build();
+                regex.map_or(Self::Invalid, Self::Regex)
+            },
+            MatchMode::Exact => literal(Anchor::Whole),
+            MatchMode::Prefix => literal(Anchor::Start),
+            MatchMode::Substring => literal(Anchor::Anywhere),
+        }
+    }
+    fn score(&self, text: &str) -> Option<i64> {
+        match self {
+            Self::Fuzzy(matcher, term) => matcher.fuzzy_match(text, term),
+            Self::Literal { .. } | Self::Regex(_) => {
+                let (start, _) = self.find(text)?;
+                Some(-i64::try_from(start).unwrap_or(i64::MAX))
+            },
+            Self::Invalid => None,
+        }
+    }
+    fn indices(&self, text: &str) -> Vec<usize> {
+        match self {
+            Self::Fuzzy(matcher, term) => {
+                matcher.fuzzy_indices(text, term).map(|(_, indices)| indices).unwrap_or_default()
+            },
+            Self::Literal { .. } | Self::Regex(_) => {
+                self.find(text).map(|(start, end)| (start .. end).collect()).unwrap_or_default()
+            },
+            Self::Invalid => Vec::new(),
+        }
+    }
+    fn find(&self, text: &str) -> Option<(usize, usize)> {
+        match self {
+            Self::Literal { anchor, term, ignore_case } => {
+                let text = text.chars().collect::<Vec<_>>();
+                let equal = |(left, right): (&char, &char)| {
+                    left == right || (*ignore_case && left.to_lowercase().eq(right.to_lowercase()))
+                };
+                let matches_at = |start: usize| {
+                    text.get(start .. start + term.len())
+                        .is_some_and(|window| window.iter().zip(term).all(equal))
+                };
+                let last = text.len().checked_sub(term.len())?;
+                let start = match anchor {
+                    Anchor::Whole => (last == 0 && matches_at(0)).then_some(0),
+                    Anchor::Start => matches_at(0).then_some(0),
+                    Anchor::End => matches_at(last).then_some(last),
+                    Anchor::Anywhere => (0 ..= last).find(|&start| matches_at(start)),
+                };
+                start.map(|start| (start, start + term.len()))
+            },
+            Self::Regex(regex) => {
+                let found = regex.find(text)?;
+                let start = text[.. found.start()].chars().count();
+                Some((start, start + found.as_str().chars().count()))
+            },
+            Self::Fuzzy(..) | Self::Invalid => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher(mode: MatchMode) -> Matcher {
+        let fields = SearchFields::from_iter([SearchField::Display]);
+        Matcher { mode, case: SearchCase::Smart, fields }
+    }
+    const fn subject(display: &str) -> Subject<'_> {
+        Subject { display, output: "", key: "" }
+    }
+    fn find(anchor: Anchor, term: &str, text: &str) -> Option<(usize, usize)> {
+        Term::Literal { anchor, term: term.chars().collect(), ignore_case: true }.find(text)
+    }
+    fn matches(query: &str, text: &str) -> bool {
+        matcher(MatchMode::Fuzzy).pattern(query).score(&subject(text)).is_some()
+    }
+
+    #[test]
+    fn literal_positions_count_characters() {
+        assert_eq!(find(Anchor::Anywhere, "über", "Größe über"), Some((6, 10)));
+        assert_eq!(find(Anchor::Start, "日本", "日本語"), Some((0, 2)));
+        assert_eq!(find(Anchor::End, "語", "日本語"), Some((2, 3)));
+        assert_eq!(find(Anchor::Whole, "ÄÖ", "äö"), Some((0, 2)));
+        assert_eq!(find(Anchor::Whole, "日本", "日本語"), None);
+        assert_eq!(find(Anchor::Anywhere, "日本語x", "日本語"), None);
+    }
+    #[test]
+    fn regex_positions_count_characters() {
+        let term = Term::new(MatchMode::Regex, "b.r", false);
+        assert_eq!(term.find("🍺 ünd bär"), Some((6, 9)));
+        assert_eq!(term.indices("🍺 ünd bär"), [6, 7, 8]);
+    }
+    #[test]
+    fn highlights_character_indices() {
+        let pattern = matcher(MatchMode::Substring).pattern("bär");
+        assert_eq!(pattern.indices("🍺 bär"), [2, 3, 4]);
+    }
+    #[test]
+    fn splits_words_on_unescaped_whitespace() {
+        assert_eq!(words("  foo\tbar  "), ["foo", "bar"]);
+        assert_eq!(words(r"foo\ bar | baz"), ["foo bar", "|", "baz"]);
+        assert_eq!(words(r"a\b"), [r"a\b"]);
+        assert!(words("   ").is_empty());
+    }
+    #[test]
+    fn applies_anchors_negations_and_alternatives() {
+        assert!(matches("^git 'push", "git push origin"));
+        assert!(!matches("^push", "git push"));
+        assert!(matches("origin$", "git push origin"));
+        assert!(!matches("git !push", "git push"));
+        assert!(matches("pull | push", "git push"));
+        assert!(!matches("pull | fetch", "git push"));
+    }
+    #[test]
+    fn restricts_prefixed_terms_to_their_field() {
+        let subject = Subject { display: "Deploy", output: "ssh prod", key: "d" };
+        let matches = |query| matcher(MatchMode::Fuzzy).pattern(query).score(&subject).is_some();
+
+        assert!(matches("out:prod"));
+        assert!(!matches("prod"));
+        assert!(matches("disp:dep key:d"));
+        assert!(!matches("!key:d"));
+    }
+    #[test]
+    fn exact_and_prefix_apply_to_unanchored_terms() {
+        let matches =
+            |mode, query, display| matcher(mode).pattern(query).score(&subject(display)).is_some();
+
+        assert!(matches(MatchMode::Exact, r"foo\ bar", "foo bar"));
+        assert!(!matches(MatchMode::Exact, r"foo\ bar", "bar foo bar"));
+        assert!(!matches(MatchMode::Exact, "foo bar", "foo bar"));
+        assert!(matches(MatchMode::Exact, "foo | bar", "bar"));
+        assert!(!matches(MatchMode::Exact, "!bar", "bar"));
+        assert!(matches(MatchMode::Prefix, "foo 'baz", "foo bar baz"));
+        assert!(!matches(MatchMode::Prefix, "foo 'baz", "bar foo baz"));
+    }
+}
@@ -1,4 +1,5 @@
 pub mod events;
+pub mod matcher;
 pub mod terminal;
 pub mod ui;
 
@@ -3,6 +3,7 @@ use ratatui::{
     style::Style,
     widgets::{Block, BorderType, Borders},
 };
+use serde::Deserialize;
 
 use crate::interface::ui::Colorscheme;
 
@@ -10,6 +11,8 @@ use crate::interface::ui::Colorscheme;
 pub struct Customizations {
     pub colorscheme: Colorscheme,
     pub border_style: BorderStyle,
+    pub title: Option<String>,
+    pub prompt: Option<String>,
 }
 impl Customizations {
     pub fn borders<'b>(&self, block: Block<'b>) -> Block<'b> {
@@ -17,7 +20,8 @@ impl Customizations {
     }
 }
 
-#[derive(Clone, Copy, ValueEnum)]
+#[derive(Clone, Copy, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum BorderStyle {
     None,
     Plain,
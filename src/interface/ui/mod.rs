@@ -4,6 +4,9 @@ pub mod input_mode;
 pub mod vector;
 pub mod widgets;
 
+use std::{mem, sync::mpsc::TryRecvError};
+
+use anyhow::{ensure, Result};
 pub use colors::Colorscheme;
 pub use customizations::Customizations;
 pub use input_mode::InputMode;
@@ -12,22 +15,69 @@ pub use vector::Vector;
 pub use widgets::list::List;
 
 use self::widgets::searchbar::Searchbar;
-use crate::parse::MenuOption;
-pub struct Ui<'o> {
-    pub list: List<'o>,
+use crate::{
+    interface::matcher::Matcher,
+    parse::{command::Reload, hotkey::KeyPolicy, stream::Stream, Menu, MenuOption},
+};
+pub struct Ui {
+    pub list: List,
     pub searchbar: Searchbar,
     pub input_mode: InputMode,
+    parents: Vec<List>,
+    stream: Option<Stream>,
+    reload: Option<Reload>,
 }
-impl<'o> Ui<'o> {
+impl Ui {
     pub fn new(
-        options: &'o [MenuOption<'static, 'static>],
+        options: Vec<MenuOption<'static, 'static>>,
         customizations: Customizations,
+        keys: KeyPolicy,
+        matcher: Matcher,
+        stream: Option<Stream>,
+        reload: Option<Reload>,
     ) -> Self {
-        let list = List::new(options, customizations.clone());
-        let searchbar = Searchbar::new(customizations);
-        let input_mode = InputMode::Selecting;
+        let keyed = stream.as_ref().is_some_and(|stream| stream.keyed);
+        let keyless = !keys.auto && !keyed && options.iter().all(|option| option.key.is_empty());
+        let mut list = List::new(options, customizations.clone(), keys);
+        list.loading = stream.is_some();
+        let searchbar = Searchbar::new(customizations, matcher);
+        let input_mode = if keyless { InputMode::Searching } else { InputMode::Selecting };
+        let parents = Vec::new();
 
-        Self { list, searchbar, input_mode }
+        Self { list, searchbar, input_mode, parents, stream, reload }
+    }
+    pub const fn is_loading(&self) -> bool {
+        self.stream.is_some()
+    }
+    pub fn receive(&mut self) -> Result<()> {
+        let Some(stream) = &self.stream
+        else {
+            return Ok(());
+        };
+        let mut arrived = Vec::new();
+        let mut failed = None;
+        let finished = loop {
+            match stream.receiver.try_recv() {
+                Ok(Ok(option)) => arrived.push(option),
+                Ok(Err(error)) => failed = failed.or_else(|| Some(error.root_cause().to_string())),
+                Err(TryRecvError::Empty) => break false,
+                Err(TryRecvError::Disconnected) => break true,
+            }
+        };
+
+        let root = self.parents.first_mut().unwrap_or(&mut self.list);
+        if !arrived.is_empty() {
+            root.extend(arrived);
+        }
+        if root.error.is_none() {
+            root.error = failed;
+        }
+        if finished {
+            self.stream = None;
+            root.loading = false;
+            ensure!(!root.is_empty() || root.error.is_some(), "No options where given.");
+        }
+        Ok(())
     }
     pub fn render<B: Backend>(&mut self, frame: &mut Frame<B>) {
         let area = frame.size();
@@ -39,7 +89,7 @@ impl<'o> Ui<'o> {
         match self.input_mode {
             InputMode::Searching => {
                 let list_bounds = Vector { x: area.width, y: area.height - query_height };
-                let list_area = self.list.dimensions.center_in(list_bounds);
+                let list_area = self.list.area_in(list_bounds, self.searchbar.width());
                 let query_area = Rect {
                     x: list_area.x,
                     y: list_area.y + list_area.height,
@@ -47,15 +97,58 @@ impl<'o> Ui<'o> {
                     width: list_area.width,
                 };
 
-                self.list.render(frame, list_area, Some(&self.searchbar.query));
+                self.list.render(frame, list_area, true);
                 self.searchbar.render(frame, query_area);
             },
             InputMode::Selecting => {
                 let list_bounds = Vector { x: area.width, y: area.height };
-                let list_area = self.list.dimensions.center_in(list_bounds);
-                self.list.render(frame, list_area, None);
+                let list_area = self.list.area_in(list_bounds, 0);
+                self.list.render(frame, list_area, false);
+            },
+        }
+    }
+    pub fn reload(&mut self) {
+        let Some(reload) = &self.reload
+        else {
+            return;
+        };
+        match reload() {
+            Ok(Menu { options, .. }) => {
+                if let Some(root) = self.parents.drain(..).next() {
+                    self.list = root;
+                }
+                let kept = self.list.replace(options.into_vec());
+                if !kept && !self.searchbar.query.is_empty() {
+                    self.update_query();
+                }
             },
+            Err(error) => self.list.error = Some(error.root_cause().to_string()),
+        }
+    }
+    pub fn descend(&mut self, index: usize) -> bool {
+        let option = self.list.option(index);
+        if option.children.is_empty() {
+            return false;
         }
+
+        let title = self
+            .list
+            .title()
+            .map_or_else(|| option.to_string(), |title| format!("{title} > {option}"));
+        let child = self.list.child(option.children.to_vec(), title);
+        let parent = mem::replace(&mut self.list, child);
+        self.parents.push(parent);
+        self.clear_query();
+        true
+    }
+    pub fn ascend(&mut self) -> bool {
+        let Some(parent) = self.parents.pop()
+        else {
+            return false;
+        };
+        self.list = parent;
+        self.clear_query();
+        true
     }
     pub fn append_query(&mut self, character: char) {
         self.searchbar.query.push(character);
@@ -66,7 +159,15 @@ impl<'o> Ui<'o> {
         self.searchbar.query.pop();
         self.update_query();
     }
+    fn clear_query(&mut self) {
+        self.searchbar.query.clear();
+        self.update_query();
+    }
+    pub fn cycle_matcher(&mut self) {
+        self.searchbar.matcher = self.searchbar.matcher.cycled();
+        self.update_query();
+    }
     fn update_query(&mut self) {
-        self.list.query(&self.searchbar.query);
+        self.list.query(&self.searchbar.query, self.searchbar.matcher);
     }
 }
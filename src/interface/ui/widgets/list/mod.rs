@@ -1,168 +1,632 @@
 pub mod state;
 
-use std::borrow::Cow;
+use std::{borrow::Cow, cmp::Reverse};
 
-use fuzzy_matcher::{skim::SkimMatcherV2, FuzzyMatcher};
 use ratatui::{
+    layout::{Alignment, Margin},
     prelude::{Backend, Rect},
     style::{Modifier, Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, List as TuiList, ListItem},
+    widgets::{
+        block::{Position, Title},
+        Block,
+        List as TuiList,
+        ListItem,
+    },
     Frame,
 };
 use textwrap::{wrap, Options};
+use unicode_width::UnicodeWidthStr;
 
 use self::state::State;
 use crate::{
-    interface::ui::{colors::CellColor, customizations::Customizations, Vector},
-    parse::MenuOption,
+    interface::{
+        matcher::{Matcher, Pattern, Subject},
+        ui::{colors::CellColor, customizations::Customizations, Vector},
+    },
+    parse::{
+        duplicates::conflicts,
+        hotkey::{DuplicateKeys, Hotkey, Key, KeyPolicy},
+        mnemonic::assign_keys,
+        Kind,
+        MenuOption,
+    },
 };
 
-pub struct List<'l> {
+const SUBMENU_MARKER: &str = " >";
+const SEPARATOR_LINE: &str = "─";
+const CONFLICT_MARKER: &str = "!";
+const COLUMN_GAP: &str = "  ";
+
+type Styles<'s> = Cow<'s, [(usize, Style)]>;
+
+pub struct List {
     pub state: State,
     pub dimensions: Vector,
-    data: &'l [MenuOption<'static, 'static>],
+    pub pending: Hotkey,
+    pub loading: bool,
+    pub error: Option<String>,
+    data: Vec<MenuOption<'static, 'static>>,
     customizations: Customizations,
+    keys: KeyPolicy,
+    conflicts: Box<[bool]>,
+    column_widths: Box<[usize]>,
+    visible: Box<[usize]>,
+    pattern: Option<Pattern>,
     area: Option<Rect>,
 }
 
-impl<'l> List<'l> {
-    pub fn new(data: &'l [MenuOption<'static, 'static>], customizations: Customizations) -> Self {
-        let length = data.len();
-        let state = State::with_length(length);
+impl List {
+    pub fn new(
+        data: Vec<MenuOption<'static, 'static>>,
+        customizations: Customizations,
+        keys: KeyPolicy,
+    ) -> Self {
+        let state = State::new(Box::default());
+        let dimensions = Vector { x: 0, y: 0 };
+        let pending = Hotkey::default();
+        let loading = false;
+        let error = None;
+        let conflicts = Box::default();
+        let column_widths = Box::default();
+        let visible = Box::default();
+        let pattern = None;
+        let area = None;
 
-        let border_size = customizations.border_style.size();
-        let width = options_width(data);
-        let height = u16::try_from(data.len()).unwrap();
-        let dimensions = Vector { x: width + border_size, y: height + border_size };
+        let mut list = Self {
+            state,
+            dimensions,
+            pending,
+            loading,
+            error,
+            data,
+            customizations,
+            keys,
+            conflicts,
+            column_widths,
+            visible,
+            pattern,
+            area,
+        };
+        list.refresh();
+        list
+    }
+    pub fn child(&self, data: Vec<MenuOption<'static, 'static>>, title: String) -> Self {
+        let customizations = Customizations { title: Some(title), ..self.customizations.clone() };
+        Self::new(data, customizations, self.keys)
+    }
+    pub fn extend(&mut self, options: Vec<MenuOption<'static, 'static>>) {
+        let selected = self.state.selected().map(|row| self.visible[row]);
+        self.data.extend(options);
+        self.refresh();
 
-        let area = None;
+        let row = match selected {
+            Some(index) => self.visible.iter().position(|&other| other == index),
+            None if self.pattern.is_some() => self.state.first(),
+            None => None,
+        };
+        self.state.select(row);
+    }
+    pub fn replace(&mut self, options: Vec<MenuOption<'static, 'static>>) -> bool {
+        let selected = self.state.selected().map(|row| self.option(row).output.clone());
+        self.data = options;
+        self.pending.clear();
+        self.error = None;
+        self.refresh();
 
-        Self { state, dimensions, data, customizations, area }
+        let row = selected.and_then(|output| {
+            let found = |&index: &usize| self.data[index].output == output;
+            self.visible.iter().position(found)
+        });
+        self.state.select(row);
+        row.is_some()
     }
-    fn create_widget(
-        options: &'l [MenuOption],
-        width: u16,
-        customizations @ Customizations { colorscheme, border_style }: &Customizations,
-        query: Option<&str>,
-    ) -> TuiList<'l> {
+    fn refresh(&mut self) {
+        if self.keys.auto {
+            assign_keys(&mut self.data, self.keys);
+        }
+        let data = &self.data;
+        self.conflicts = conflicts(data, self.keys.case);
+        self.column_widths = column_widths(data);
+
+        let border_size = self.customizations.border_style.size();
+        let width = options_width(data, &self.conflicts, &self.column_widths);
+        let height = data.iter().map(|option| option.display.lines().count().max(1)).sum::<usize>();
+        let height = u16::try_from(height).unwrap_or(u16::MAX);
+        self.dimensions =
+            Vector { x: width.saturating_add(border_size), y: height.saturating_add(border_size) };
+        self.filter();
+    }
+    fn filter(&mut self) {
+        self.visible = match &self.pattern {
+            None => (0 .. self.data.len()).collect(),
+            Some(pattern) => {
+                let score = |option: &MenuOption| {
+                    let key = option.key.to_string();
+                    let (display, output) = (&option.display, &option.output);
+                    pattern.score(&Subject { display, output, key: &key })
+                };
+                let mut ranked = self
+                    .data
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, option)| option.is_selectable())
+                    .filter_map(|(index, option)| score(option).map(|score| (index, score)))
+                    .collect::<Vec<_>>();
+                ranked.sort_by_key(|&(index, score)| (Reverse(score), index));
+                ranked.into_iter().map(|(index, _)| index).collect()
+            },
+        };
+        let selectable = self.visible.iter().map(|&index| self.data[index].is_selectable());
+        self.state.selectable = selectable.collect();
+    }
+    pub fn area_in(&self, bounds: Vector, minimum_width: u16) -> Rect {
+        let border_size = self.customizations.border_style.size();
+        let error = self.error.as_ref().map(|error| format!(" {error} "));
+        let status = error.or_else(|| self.status()).map_or(0, |status| status.width());
+        let counter = self.counter().map_or(0, |counter| counter.width());
+        let footer = u16::try_from(status + counter + 3).unwrap_or(u16::MAX);
+        let width = self.dimensions.x.max(footer).max(minimum_width).min(bounds.x);
+        let item_width = width.saturating_sub(border_size);
+        let key_width = key_width(&self.data, &self.conflicts);
+        let rows = self.data.iter().map(|option| self.height(option, item_width, key_width));
+        let height = u16::try_from(rows.sum::<usize>()).unwrap_or(u16::MAX);
+        Vector { x: width, y: height.saturating_add(border_size) }.center_in(bounds)
+    }
+    fn height(&self, option: &MenuOption, item_width: u16, key_width: usize) -> usize {
+        match option.kind {
+            Kind::Selectable => {
+                let display = aligned(option, &self.column_widths).0;
+                let indent = " ".repeat(key_width + 3);
+                let options = Options::new(text_width(option, item_width, key_width));
+                wrap(&display, options.subsequent_indent(&indent)).len()
+            },
+            Kind::Heading | Kind::Separator => 1,
+        }
+    }
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+    pub fn option(&self, row: usize) -> &MenuOption<'static, 'static> {
+        &self.data[self.visible[row]]
+    }
+    pub fn title(&self) -> Option<&str> {
+        self.customizations.title.as_deref()
+    }
+    fn create_widget(&self, width: u16, highlight: bool) -> TuiList<'static> {
+        let Self { data: options, customizations, pending, conflicts, column_widths, .. } = self;
+        let Customizations { colorscheme, border_style, title, .. } = customizations;
+
         let highlight_style = Style::default()
             .add_modifier(Modifier::BOLD)
             .bg(colorscheme.selected.background)
             .fg(colorscheme.selected.foreground);
 
+        let pattern = self.pattern.as_ref().filter(|_| highlight);
         let item_width = width - border_style.size();
-        let items = options
+        let key_width = key_width(options, conflicts);
+        let items = self
+            .visible
             .iter()
-            .map(|text| Self::create_item(text, item_width, &colorscheme.key, query))
+            .map(|&index| (&options[index], conflicts[index]))
+            .map(|(option, conflicting)| match option.kind {
+                Kind::Selectable => {
+                    let dimmed = !option.key.starts_with(pending, self.keys.case);
+                    let key_color = &colorscheme.key;
+                    let key_column = (key_width, key_color, conflicting);
+                    let text = aligned(option, column_widths);
+                    Self::create_item(option, text, item_width, key_column, pattern, dimmed)
+                },
+                Kind::Heading => Self::create_heading(option),
+                Kind::Separator => Self::create_separator(item_width),
+            })
             .collect::<Vec<_>>();
         let block = customizations.borders(Block::default());
+        let block = match title {
+            Some(title) => block.title(title.clone()),
+            None => block,
+        };
+        let error = |error| Span::styled(format!(" {error} "), Style::default().red());
+        let block = match self.error.as_ref().map(error).or_else(|| self.status().map(Span::raw)) {
+            Some(status) => block.title(Title::from(status).position(Position::Bottom)),
+            None => block,
+        };
+        let block = match self.counter() {
+            Some(counter) => {
+                let counter = Title::from(counter).alignment(Alignment::Right);
+                block.title(counter.position(Position::Bottom))
+            },
+            None => block,
+        };
         TuiList::new(items).highlight_style(highlight_style).block(block)
     }
+    fn status(&self) -> Option<String> {
+        let pattern = self.pattern.as_ref()?;
+        let option = self.option(self.state.selected()?);
+        let key = option.key.to_string();
+        let subject = Subject { display: &option.display, output: &option.output, key: &key };
+        let fields = pattern.hidden_matches(&subject);
+        let matches = fields.iter().map(|&field| format!("{field}: {}", subject.field(field)));
+        (!fields.is_empty()).then(|| format!(" {} ", matches.collect::<Vec<_>>().join(", ")))
+    }
+    fn counter(&self) -> Option<String> {
+        let count = if self.pattern.is_none() {
+            self.data.len().to_string()
+        }
+        else {
+            let total = self.data.iter().filter(|option| option.is_selectable()).count();
+            format!("{}/{total}", self.visible.len())
+        };
+        match (self.loading, self.pattern.is_none()) {
+            (true, _) => Some(format!(" Loading… {count} ")),
+            (false, false) => Some(format!(" {count} ")),
+            (false, true) => None,
+        }
+    }
     fn create_item(
-        option: &'l MenuOption,
+        option: &MenuOption,
+        (display, mnemonic, styles): (Cow<str>, Option<usize>, Styles),
         width: u16,
-        key_color: &CellColor,
-        query: Option<&str>,
-    ) -> ListItem<'l> {
-        let MenuOption { key, output: _, display } = option;
+        (key_width, key_color, conflicting): (usize, &CellColor, bool),
+        pattern: Option<&Pattern>,
+        dimmed: bool,
+    ) -> ListItem<'static> {
+        let MenuOption { key, children, .. } = option;
 
-        let default_style = Style::default();
+        let default_style =
+            if dimmed { Style::default().add_modifier(Modifier::DIM) } else { Style::default() };
         let display_style = default_style;
         let key_style = default_style
             .add_modifier(Modifier::BOLD)
             .fg(key_color.foreground)
             .bg(key_color.background);
 
-        let wrap = wrap(
-            display,
-            Options::new(usize::try_from(width - 4).unwrap()).subsequent_indent("    "),
-        );
-        let mut wrapped_display =
-            wrap.into_iter().map(|line| Self::style_display(line, query, display_style));
-        let mut first_line =
-            vec![Span::styled(format!(" {key} "), key_style), Span::styled(" ", display_style)];
-        first_line.extend(wrapped_display.next().unwrap());
+        let indent = " ".repeat(key_width + 3);
+        let marker = if children.is_empty() { "" } else { SUBMENU_MARKER };
+        let options = Options::new(text_width(option, width, key_width));
+        let lines = wrap(&display, options.subsequent_indent(&indent));
+        let conflict = if conflicting { CONFLICT_MARKER } else { "" };
+        let key = format!("{key}{conflict}");
+        let padding = " ".repeat(key_width.saturating_sub(key.width()));
+        let indices = pattern.map(|pattern| pattern.indices(&display)).unwrap_or_default();
 
-        let mut text = Vec::with_capacity(2);
-        text.push(Line::from(first_line));
-        text.extend(wrapped_display.map(Line::from));
+        let mut offset = 0;
+        let mut text = Vec::with_capacity(lines.len());
+        for (number, line) in lines.iter().enumerate() {
+            let (mut spans, content) = if number == 0 {
+                let key = Span::styled(format!(" {key}{padding} "), key_style);
+                (vec![key, Span::styled(" ", display_style)], line.as_ref())
+            }
+            else {
+                let content = line.strip_prefix(indent.as_str()).unwrap_or(line);
+                (vec![Span::styled(indent.clone(), display_style)], content)
+            };
+            let start = display[offset ..].find(content).map_or(offset, |found| offset + found);
+            offset = start + content.len();
+            let position = display[.. start].chars().count();
+            let decorations = (mnemonic, styles.as_ref(), indices.as_slice());
+            spans.extend(Self::style_display(content, position, decorations, display_style));
+            text.push(Line::from(spans));
+        }
+        if let Some(last) = text.last_mut() {
+            last.spans.push(Span::styled(marker, display_style));
+        }
 
         ListItem::new(text)
     }
-    fn style_display<'s>(string: Cow<'s, str>, query: Option<&str>, style: Style) -> Vec<Span<'s>> {
-        let matches = |query| SkimMatcherV2::default().fuzzy_indices(&string, query);
-        if let Some((_, indices)) = query.and_then(matches) {
-            let highlight_style = style.red();
-            string
-                .char_indices()
-                .map(|(index, character)| {
-                    if indices.contains(&index) {
-                        Span::styled(String::from(character), highlight_style)
-                    }
-                    else {
-                        Span::styled(String::from(character), style)
-                    }
-                })
-                .collect()
-        }
-        else {
-            vec![Span::styled(string, style)]
+    fn create_heading(option: &MenuOption) -> ListItem<'static> {
+        let style = Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+        let spans = vec![Span::raw(" "), Span::styled(option.display.to_string(), style)];
+        ListItem::new(Line::from(spans))
+    }
+    fn create_separator(width: u16) -> ListItem<'static> {
+        let style = Style::default().add_modifier(Modifier::DIM);
+        ListItem::new(Line::from(Span::styled(SEPARATOR_LINE.repeat(usize::from(width)), style)))
+    }
+    fn style_display(
+        string: &str,
+        start: usize,
+        (mnemonic, styles, indices): (Option<usize>, &[(usize, Style)], &[usize]),
+        style: Style,
+    ) -> Vec<Span<'static>> {
+        if indices.is_empty() && mnemonic.is_none() && styles.is_empty() {
+            return vec![Span::styled(String::from(string), style)];
         }
+
+        let style_at = |position| {
+            let ansi = styles.iter().rev().find(|&&(from, _)| from <= position);
+            ansi.map_or(style, |&(_, ansi)| style.patch(ansi))
+        };
+        string
+            .chars()
+            .enumerate()
+            .map(|(offset, character)| {
+                let position = start + offset;
+                let mut character_style = style_at(position);
+                if indices.contains(&position) {
+                    character_style = character_style.red();
+                }
+                if mnemonic == Some(position) {
+                    character_style =
+                        character_style.add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+                }
+                Span::styled(String::from(character), character_style)
+            })
+            .collect()
     }
-    pub fn render<B: Backend>(&mut self, frame: &mut Frame<B>, area: Rect, query: Option<&str>) {
-        let widget = Self::create_widget(self.data, area.width, &self.customizations, query);
+    pub fn render<B: Backend>(&mut self, frame: &mut Frame<B>, area: Rect, highlight: bool) {
+        let widget = self.create_widget(area.width, highlight);
         let state = &mut self.state.inner;
 
         frame.render_stateful_widget(widget, area, state);
         self.area = Some(area);
     }
     pub fn select(&mut self, coordinate: Vector) -> Option<usize> {
-        let position = self.area.and_then(|area| Self::row_in_area(area, coordinate));
+        let position = self
+            .area
+            .and_then(|area| self.row_in_area(area, coordinate))
+            .filter(|&index| self.state.is_selectable(index));
         if position == self.state.selected() {
             position
         }
         else {
-            self.state.inner.select(position);
+            self.state.select(position);
             None
         }
     }
-    fn row_in_area(area: Rect, Vector { x, y }: Vector) -> Option<usize> {
-        (area.x ..= area.x + area.width).contains(&x).then_some((y - area.y - 1) as usize)
-    }
-    pub fn query(&mut self, term: &str) {
-        let matcher = SkimMatcherV2::default();
-        let score = |subject: String| matcher.fuzzy_match(&subject, term);
-        let max = |holder: Option<(usize, i64)>, contender| {
-            holder.zip(contender).map_or_else(
-                || holder.or(contender),
-                |(holder, contender)| {
-                    if contender.1 > holder.1 {
-                        Some(contender)
-                    }
-                    else {
-                        Some(holder)
-                    }
-                },
-            )
+    fn row_in_area(&self, area: Rect, Vector { x, y }: Vector) -> Option<usize> {
+        let border_size = self.customizations.border_style.size();
+        let inner = area.inner(&Margin::new(border_size / 2, border_size / 2));
+        let inside =
+            (inner.x .. inner.right()).contains(&x) && (inner.y .. inner.bottom()).contains(&y);
+        if !inside {
+            return None;
+        }
+
+        let line = usize::from(y - inner.y);
+        let key_width = key_width(&self.data, &self.conflicts);
+        let mut bottom = 0;
+        (self.state.inner.offset() .. self.visible.len()).find(|&row| {
+            bottom += self.height(self.option(row), inner.width, key_width);
+            line < bottom
+        })
+    }
+    pub fn is_bound(&self, key: Key) -> bool {
+        let mut chord = self.pending.clone();
+        chord.push(key);
+        let bound = |&index: &usize| {
+            let option = &self.data[index];
+            option.is_selectable() && option.key.starts_with(&chord, self.keys.case)
         };
-        let index = self
-            .data
+        self.visible.iter().any(bound)
+    }
+    pub fn press(&mut self, key: Key) -> Option<usize> {
+        self.pending.push(key);
+
+        let KeyPolicy { case, duplicates, .. } = self.keys;
+        let candidates = (0 .. self.visible.len())
+            .filter(|&row| self.state.is_selectable(row))
+            .filter(|&row| self.option(row).key.starts_with(&self.pending, case))
+            .collect::<Vec<_>>();
+        let exact = candidates
             .iter()
-            .enumerate()
-            .map(|(index, option)| score(option.to_string()).map(|score| (index, score)))
-            .reduce(max)
-            .unwrap()
-            .map(|(index, _)| index);
-        self.state.inner.select(index);
+            .copied()
+            .filter(|&row| self.option(row).key.matches(&self.pending, case))
+            .collect::<Vec<_>>();
+
+        if candidates.len() != exact.len() {
+            self.state.select(exact.first().or_else(|| candidates.first()).copied());
+            return None;
+        }
+        self.pending.clear();
+        match (duplicates, exact.as_slice()) {
+            (DuplicateKeys::Cycle, [first, _, ..]) => {
+                let selected = self.state.selected();
+                let next = exact.iter().find(|&&row| Some(row) > selected).unwrap_or(first);
+                self.state.select(Some(*next));
+                None
+            },
+            (_, exact) => exact.first().copied(),
+        }
     }
+    pub fn query(&mut self, term: &str, matcher: Matcher) {
+        let selected = self.state.selected().map(|row| self.visible[row]);
+        self.pattern = (!term.is_empty()).then(|| matcher.pattern(term));
+        self.filter();
+
+        let row = if term.is_empty() {
+            selected.and_then(|index| self.visible.iter().position(|&other| other == index))
+        }
+        else {
+            self.state.first()
+        };
+        self.state.select(row);
+    }
+}
+
+fn key_width(options: &[MenuOption], conflicts: &[bool]) -> usize {
+    let to_length = |(option, &conflicting): (&MenuOption, &bool)| {
+        let marker = if conflicting { CONFLICT_MARKER.len() } else { 0 };
+        option.key.to_string().width() + marker
+    };
+    options.iter().zip(conflicts).map(to_length).max().unwrap_or(1)
+}
+fn text_width(option: &MenuOption, item_width: u16, key_width: usize) -> usize {
+    let marker = if option.children.is_empty() { 0 } else { SUBMENU_MARKER.len() };
+    usize::from(item_width).saturating_sub(key_width + 3 + marker).max(1)
+}
+fn column_widths(options: &[MenuOption]) -> Box<[usize]> {
+    let mut widths = Vec::new();
+    for option in options {
+        for (index, column) in option.columns.iter().enumerate() {
+            let width = column.width();
+            match widths.get_mut(index) {
+                Some(current) => *current = width.max(*current),
+                None => widths.push(width),
+            }
+        }
+    }
+    widths.into_boxed_slice()
+}
+fn aligned<'o>(
+    option: &'o MenuOption,
+    widths: &[usize],
+) -> (Cow<'o, str>, Option<usize>, Styles<'o>) {
+    if option.columns.is_empty() {
+        let styles = Cow::Borrowed(option.styles.as_ref());
+        return (Cow::Borrowed(option.display.as_ref()), option.mnemonic, styles);
+    }
+
+    let mut text = String::new();
+    let mut starts = Vec::with_capacity(option.columns.len());
+    let last = option.columns.len() - 1;
+    for (index, (column, width)) in option.columns.iter().zip(widths).enumerate() {
+        starts.push(text.chars().count());
+        text.push_str(column);
+        if index != last {
+            text.push_str(&" ".repeat(width - column.width()));
+            text.push_str(COLUMN_GAP);
+        }
+    }
+
+    let align = |position: usize| {
+        let mut offset = position;
+        for (column, start) in option.columns.iter().zip(&starts) {
+            let length = column.chars().count();
+            if offset <= length {
+                return start + offset;
+            }
+            offset -= length + 1;
+        }
+        position
+    };
+    let mnemonic = option.mnemonic.map(align);
+    let styles = option.styles.iter().map(|&(position, style)| (align(position), style)).collect();
+    (Cow::Owned(text), mnemonic, Cow::Owned(styles))
+}
+fn options_width(options: &[MenuOption], conflicts: &[bool], column_widths: &[usize]) -> u16 {
+    let to_lengths = |option: &MenuOption| {
+        let marker = if option.children.is_empty() { 0 } else { SUBMENU_MARKER.len() };
+        let display = aligned(option, column_widths).0;
+        display.lines().map(UnicodeWidthStr::width).max().unwrap_or(0) + marker
+    };
+
+    let key_chars = key_width(options, conflicts) + 2;
+    let display_chars = options.iter().map(to_lengths).max().unwrap_or(0) + 1;
+
+    u16::try_from(display_chars + key_chars).unwrap_or(u16::MAX)
 }
 
-fn options_width(options: &[MenuOption]) -> u16 {
-    let to_lengths = |option: &MenuOption| option.to_string().chars().count();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        interface::{
+            matcher::{MatchMode, SearchCase, SearchField, SearchFields},
+            ui::{
+                colors::{Color, Colorscheme},
+                customizations::BorderStyle,
+            },
+        },
+        parse::hotkey::{DuplicateKeys, Key, KeyCase, KeyPolicy},
+    };
+
+    fn options(lines: &[&str]) -> Vec<MenuOption<'static, 'static>> {
+        lines.iter().map(|line| line.parse().unwrap()).collect()
+    }
+    fn keys(case: KeyCase, duplicates: DuplicateKeys) -> KeyPolicy {
+        KeyPolicy { case, duplicates, auto: false }
+    }
+    fn customizations() -> Customizations {
+        let color = CellColor { foreground: Color::Reset, background: Color::Reset };
+        let colorscheme = Colorscheme { selected: color.clone(), key: color, border: Color::Reset };
+        Customizations { colorscheme, border_style: BorderStyle::Plain, title: None, prompt: None }
+    }
+
+    #[test]
+    fn chords_choose_on_their_last_key() {
+        let options = options(&["g : go", "gc : commit", "gp : push", "x : exit"]);
+        let mut list =
+            List::new(options, customizations(), keys(KeyCase::Sensitive, DuplicateKeys::Error));
 
-    let key_chars = 3;
-    let display_chars = options.iter().map(to_lengths).max().unwrap() + 1;
+        assert_eq!(list.press(Key::plain('g')), None);
+        assert_eq!(list.state.selected(), Some(0));
+        assert_eq!(list.press(Key::plain('p')), Some(2));
+        assert!(list.pending.is_empty());
+        assert_eq!(list.press(Key::plain('x')), Some(3));
+    }
+    #[test]
+    fn unbound_keys_reset_the_chord() {
+        let options = options(&["ab : x"]);
+        let mut list =
+            List::new(options, customizations(), keys(KeyCase::Sensitive, DuplicateKeys::Error));
+
+        assert_eq!(list.press(Key::plain('a')), None);
+        assert_eq!(list.press(Key::plain('z')), None);
+        assert!(list.pending.is_empty());
+        assert_eq!(list.press(Key::plain('a')), None);
+        assert_eq!(list.press(Key::plain('b')), Some(0));
+    }
+    #[test]
+    fn insensitive_keys_ignore_case() {
+        let options = options(&["gC : commit", "gp : push"]);
+        let mut list =
+            List::new(options, customizations(), keys(KeyCase::Insensitive, DuplicateKeys::Error));
 
-    (display_chars + key_chars).try_into().unwrap()
+        assert_eq!(list.press(Key::plain('G')), None);
+        assert_eq!(list.press(Key::plain('c')), Some(0));
+    }
+    #[test]
+    fn cycle_moves_through_duplicates() {
+        let options = options(&["a : x", "b : y", "a : z"]);
+        let mut list =
+            List::new(options, customizations(), keys(KeyCase::Sensitive, DuplicateKeys::Cycle));
+
+        let mut selected = Vec::new();
+        for _ in 0 .. 3 {
+            assert_eq!(list.press(Key::plain('a')), None);
+            selected.extend(list.state.selected());
+        }
+        assert_eq!(selected, [0, 2, 0]);
+        assert_eq!(list.press(Key::plain('b')), Some(1));
+    }
+    #[test]
+    fn other_policies_choose_the_first_duplicate() {
+        let options = options(&["b : y", "a : x", "a : z"]);
+        let mut list =
+            List::new(options, customizations(), keys(KeyCase::Sensitive, DuplicateKeys::First));
+
+        assert_eq!(list.press(Key::plain('a')), Some(1));
+    }
+    #[test]
+    fn styles_display_by_character_position() {
+        let ansi = Style::default().italic();
+        let decorations = (Some(3), &[(2, ansi)][..], &[2][..]);
+        let spans = List::style_display("日本語", 1, decorations, Style::default());
+
+        let contents = spans.iter().map(|span| span.content.as_ref()).collect::<Vec<_>>();
+        assert_eq!(contents, ["日", "本", "語"]);
+        assert_eq!(spans[0].style, Style::default());
+        assert_eq!(spans[1].style, ansi.red());
+        assert_eq!(spans[2].style, ansi.add_modifier(Modifier::BOLD | Modifier::UNDERLINED));
+    }
+    #[test]
+    fn saturates_the_width_of_long_options() {
+        let options = options(&[&format!("a : {}", "x".repeat(70_000))]);
+        assert_eq!(options_width(&options, &[false], &[]), u16::MAX);
+    }
+    #[test]
+    fn extending_keeps_the_selected_option() {
+        let fields = SearchFields::from_iter([SearchField::Display]);
+        let matcher = Matcher { mode: MatchMode::Substring, case: SearchCase::Smart, fields };
+        let policy = keys(KeyCase::Sensitive, DuplicateKeys::Error);
+        let mut list = List::new(options(&["b : beta", "a : alpha"]), customizations(), policy);
+
+        list.query("a", matcher);
+        assert_eq!(list.state.selected(), Some(0));
+        list.state.next();
+        list.extend(options(&["g : gamma", "d : delta"]));
+        assert_eq!(*list.visible, [1, 2, 0, 3]);
+        assert_eq!(list.state.selected(), Some(2));
+    }
 }
@@ -1,22 +1,39 @@
 use ratatui::widgets::ListState;
 
 pub struct State {
-    pub length: usize,
+    pub selectable: Box<[bool]>,
     pub inner: ListState,
 }
 
 impl State {
-    pub fn with_length(length: usize) -> Self {
-        Self { length, inner: ListState::default() }
+    pub fn new(selectable: Box<[bool]>) -> Self {
+        Self { selectable, inner: ListState::default() }
     }
     pub fn next(&mut self) {
-        let new = self.inner.selected().map_or(0, |index| (index + 1) % self.length);
-        self.inner.select(Some(new));
+        let length = self.selectable.len();
+        let start = self.inner.selected().map_or(0, |index| index + 1);
+        let new = (start .. start + length)
+            .map(|index| index % length)
+            .find(|&index| self.is_selectable(index));
+        if new.is_some() {
+            self.inner.select(new);
+        }
     }
     pub fn previous(&mut self) {
-        let last = self.length - 1;
-        let new = self.inner.selected().map_or(last, |index| index.checked_sub(1).unwrap_or(last));
-        self.inner.select(Some(new));
+        let length = self.selectable.len();
+        let start = self.inner.selected().unwrap_or(0) + length;
+        let new = (1 ..= length)
+            .map(|offset| (start - offset) % length)
+            .find(|&index| self.is_selectable(index));
+        if new.is_some() {
+            self.inner.select(new);
+        }
+    }
+    pub fn first(&self) -> Option<usize> {
+        (0 .. self.selectable.len()).find(|&index| self.is_selectable(index))
+    }
+    pub fn select(&mut self, index: Option<usize>) {
+        self.inner.select(index.filter(|&index| self.is_selectable(index)));
     }
     pub fn unselect(&mut self) {
         self.inner.select(None);
@@ -24,4 +41,50 @@ impl State {
     pub fn selected(&self) -> Option<usize> {
         self.inner.selected()
     }
+    pub fn is_selectable(&self, index: usize) -> bool {
+        self.selectable.get(index).copied().unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> State {
+        State::new(Box::new([false, true, true, false, true]))
+    }
+
+    #[test]
+    fn next_skips_headings_and_wraps() {
+        let mut state = state();
+        let mut visited = Vec::new();
+        for _ in 0 .. 4 {
+            state.next();
+            visited.extend(state.selected());
+        }
+        assert_eq!(visited, [1, 2, 4, 1]);
+    }
+    #[test]
+    fn previous_skips_headings_and_wraps() {
+        let mut state = state();
+        let mut visited = Vec::new();
+        for _ in 0 .. 4 {
+            state.previous();
+            visited.extend(state.selected());
+        }
+        assert_eq!(visited, [4, 2, 1, 4]);
+    }
+    #[test]
+    fn never_selects_headings() {
+        let mut state = state();
+        assert_eq!(state.first(), Some(1));
+        state.select(Some(3));
+        assert_eq!(state.selected(), None);
+
+        let mut headings = State::new(Box::new([false, false]));
+        headings.next();
+        headings.previous();
+        assert_eq!(headings.selected(), None);
+        assert_eq!(headings.first(), None);
+    }
 }
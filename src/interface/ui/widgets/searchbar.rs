@@ -1,23 +1,32 @@
 use ratatui::{
+    layout::Alignment,
     prelude::{Backend, Rect},
-    widgets::{Block, Paragraph},
+    widgets::{block::Title, Block, Paragraph},
     Frame,
 };
+use unicode_width::UnicodeWidthStr;
 
-use crate::interface::ui::Customizations;
+use crate::interface::{matcher::Matcher, ui::Customizations};
 
 pub struct Searchbar {
     pub query: String,
+    pub matcher: Matcher,
     customizations: Customizations,
 }
 
 impl Searchbar {
-    pub const fn new(customizations: Customizations) -> Self {
+    pub const fn new(customizations: Customizations, matcher: Matcher) -> Self {
         let string = String::new();
-        Self { query: string, customizations }
+        Self { query: string, matcher, customizations }
     }
     pub fn render<B: Backend>(&self, frame: &mut Frame<B>, destination: Rect) {
         let block = self.customizations.borders(Block::new());
+        let block = match &self.customizations.prompt {
+            Some(prompt) => block.title(prompt.as_str()),
+            None => block,
+        };
+        let mode = Title::from(format!(" {} ", self.matcher.mode)).alignment(Alignment::Right);
+        let block = block.title(mode);
         let scroll = self.scroll(destination.width);
         let widget = Paragraph::new(self.query.as_str()).block(block).scroll((0, scroll));
         frame.render_widget(widget, destination);
@@ -26,13 +35,52 @@ impl Searchbar {
         let y = destination.y + 1;
         frame.set_cursor(x, y);
     }
+    pub fn width(&self) -> u16 {
+        let prompt = self.customizations.prompt.as_deref().map_or(0, UnicodeWidthStr::width);
+        let mode = self.matcher.mode.to_string().len() + 2;
+        let borders = usize::from(self.customizations.border_style.size());
+        u16::try_from(prompt + mode + borders + 1).unwrap_or(u16::MAX)
+    }
     fn cursor(&self, width: u16) -> u16 {
-        let length = u16::try_from(self.query.len()).unwrap();
+        let length = u16::try_from(self.query.width()).unwrap();
         (length + 1).min(width - 3)
     }
     fn scroll(&self, width: u16) -> u16 {
-        let length = i64::try_from(self.query.len()).unwrap();
+        let length = i64::try_from(self.query.width()).unwrap();
         let scroll = (length - i64::from(width) + 4).max(0);
         u16::try_from(scroll).unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::{
+        matcher::{MatchMode, SearchCase, SearchFields},
+        ui::{
+            colors::{CellColor, Color, Colorscheme},
+            customizations::BorderStyle,
+        },
+    };
+
+    fn searchbar(query: &str) -> Searchbar {
+        let color = CellColor { foreground: Color::Reset, background: Color::Reset };
+        let colorscheme = Colorscheme { selected: color.clone(), key: color, border: Color::Reset };
+        let border_style = BorderStyle::Plain;
+        let customizations =
+            Customizations { colorscheme, border_style, title: None, prompt: None };
+        let fields = SearchFields::default();
+        let matcher = Matcher { mode: MatchMode::Fuzzy, case: SearchCase::Smart, fields };
+        Searchbar { query: String::from(query), matcher, customizations }
+    }
+
+    #[test]
+    fn cursor_follows_display_width() {
+        assert_eq!(searchbar("abc").cursor(20), 4);
+        assert_eq!(searchbar("äöü").cursor(20), 4);
+        assert_eq!(searchbar("日本").cursor(20), 5);
+        assert_eq!(searchbar("日本語日本語日本語").cursor(10), 7);
+        assert_eq!(searchbar("日本語日本語日本語").scroll(10), 12);
+        assert_eq!(searchbar("äöü").scroll(10), 0);
+    }
+}
@@ -9,7 +9,6 @@ mod parse;
 
 use anyhow::Result;
 use args::Cli;
-use clap::Parser;
 pub use config::Config;
 use interface::{
     events::{event_loop, Choice},
@@ -17,28 +16,115 @@ use interface::{
     Terminal,
     Ui,
 };
-use parse::{from_file, MenuOption};
+use parse::{
+    ansi::apply_ansi,
+    columns::Columns,
+    command::{from_command, Reload},
+    document::from_toml,
+    duplicates::check_duplicates,
+    from_arguments,
+    from_files,
+    from_tables,
+    mnemonic::assign_keys,
+    stream::{starts_with_header, stream_stdin, Stream},
+    Header,
+    InputFormat,
+    Menu,
+    MenuOption,
+};
 const PROGRAM_NAME: &str = "quick-menu";
 
 #[unix_sigpipe = "inherit"]
 fn main() -> Result<()> {
     let configuration = confy::load::<Config>(PROGRAM_NAME, None)?;
-    let arguments = Cli::parse();
-    let options = from_file(arguments.options_file.as_deref())?;
+    let (mut arguments, matches) = Cli::parse_with_matches()?;
+    let reload = reload(&arguments);
+    let (Menu { header, mut options }, stream) = match &reload {
+        Some(reload) => (reload()?, None),
+        None => load(&arguments)?,
+    };
+    arguments.apply_header(header, &matches);
+    if arguments.auto_keys {
+        assign_keys(&mut options, arguments.key_policy());
+    }
+    check_duplicates(&options, arguments.key_policy())?;
+    if arguments.check {
+        return Ok(());
+    }
 
-    let ui = create_ui(arguments, &options);
-    let choice = run_ui(ui, &options, &configuration)?;
+    let print0 = arguments.print0;
+    let ui = create_ui(arguments, options.into_vec(), stream, reload);
+    let choice = run_ui(ui, &configuration)?;
 
-    choice.print(&options)
+    choice.print(print0)
+}
+fn reload(arguments: &Cli) -> Option<Reload> {
+    let command = arguments.source_command.clone()?;
+    let format = arguments.input_format();
+    let columns = arguments.columns();
+    let read0 = arguments.read0;
+    let ansi = arguments.ansi;
+    Some(Box::new(move || {
+        let mut menu = from_command(&command, format, columns.as_ref(), read0)?;
+        if ansi {
+            apply_ansi(&mut menu.options);
+        }
+        Ok(menu)
+    }))
+}
+fn load(arguments: &Cli) -> Result<(Menu, Option<Stream>)> {
+    let paths = &arguments.options_file;
+    let columns = arguments.columns();
+    let read0 = arguments.read0;
+    let format = arguments.input_format();
+    let positional = &arguments.options;
+    let stdin = paths.is_empty() && positional.is_empty();
+    let ansi = arguments.ansi;
+    let streamed = stdin && !arguments.check && !arguments.duplicate_keys.is_reported();
+    let empty = || Menu { header: Header::default(), options: Box::default() };
+    let menu = match (&arguments.menu, columns) {
+        (Some(menu), _) => from_toml(menu)?,
+        (None, columns) if streamed && !has_header(format, columns.as_ref())? => {
+            return Ok((empty(), Some(stream_stdin(format, columns, read0, ansi))));
+        },
+        (None, _) if paths.is_empty() && !positional.is_empty() => empty(),
+        (None, Some(columns)) => from_tables(paths, &columns, read0)?,
+        (None, None) => from_files(paths, format, read0)?,
+    };
+    let mut menu = if positional.is_empty() {
+        menu
+    }
+    else {
+        menu.merge(from_arguments(positional, format, arguments.columns().as_ref())?)
+    };
+    if ansi {
+        apply_ansi(&mut menu.options);
+    }
+    Ok((menu, None))
+}
+fn has_header(format: InputFormat, columns: Option<&Columns>) -> Result<bool> {
+    match (format, columns) {
+        (InputFormat::Lines, None) => starts_with_header(),
+        _ => Ok(false),
+    }
 }
-fn run_ui(ui: Ui, options: &[MenuOption], configuration: &Config) -> Result<Choice> {
-    let event_loop = |terminal: &mut _| event_loop(terminal, ui, options, configuration);
+fn run_ui(ui: Ui, configuration: &Config) -> Result<Choice> {
+    let event_loop = |terminal: &mut _| event_loop(terminal, ui, configuration);
     Terminal::inside(event_loop)?
 }
-fn create_ui<'o>(arguments: Cli, options: &'o [MenuOption<'static, 'static>]) -> Ui<'o> {
+fn create_ui(
+    arguments: Cli,
+    options: Vec<MenuOption<'static, 'static>>,
+    stream: Option<Stream>,
+    reload: Option<Reload>,
+) -> Ui {
     let border_style = arguments.border_style;
+    let keys = arguments.key_policy();
+    let matcher = arguments.matcher();
+    let title = arguments.title.clone();
+    let prompt = arguments.prompt.clone();
     let colorscheme = Colorscheme::from_args(arguments);
-    let customizations = Customizations { colorscheme, border_style };
+    let customizations = Customizations { colorscheme, border_style, title, prompt };
 
-    Ui::new(options, customizations)
+    Ui::new(options, customizations, keys, matcher, stream, reload)
 }
@@ -0,0 +1,134 @@
+use std::borrow::Cow;
+
+use ratatui::style::{Color, Modifier, Style};
+
+use super::MenuOption;
+
+const ESCAPE: char = '\x1b';
+const BELL: char = '\x07';
+
+pub fn apply_ansi(options: &mut [MenuOption]) {
+    for option in options {
+        let (display, styles) = strip(&option.display);
+        option.display = Cow::Owned(display);
+        option.styles = styles.into_boxed_slice();
+        option.output = Cow::Owned(strip(&option.output).0);
+        for column in &mut option.columns {
+            *column = strip(column).0;
+        }
+        apply_ansi(&mut option.children);
+    }
+}
+fn strip(text: &str) -> (String, Vec<(usize, Style)>) {
+    let mut stripped = String::with_capacity(text.len());
+    let mut styles: Vec<(usize, Style)> = Vec::new();
+    let mut style = Style::default();
+    let mut position = 0;
+
+    let mut chars = text.chars().peekable();
+    while let Some(character) = chars.next() {
+        if character != ESCAPE {
+            stripped.push(character);
+            position += 1;
+            continue;
+        }
+        match chars.next() {
+            Some('[') => {
+                let mut parameters = String::new();
+                let terminator = chars.by_ref().find(|&character| {
+                    let terminates = ('\x40' ..= '\x7e').contains(&character);
+                    if !terminates {
+                        parameters.push(character);
+                    }
+                    terminates
+                });
+                if terminator == Some('m') {
+                    style = select_graphic_rendition(style, &parameters);
+                    match styles.last_mut() {
+                        Some(last) if last.0 == position => last.1 = style,
+                        _ => styles.push((position, style)),
+                    }
+                }
+            },
+            Some(']') => {
+                while let Some(character) = chars.next() {
+                    if character == BELL {
+                        break;
+                    }
+                    if character == ESCAPE {
+                        chars.next_if_eq(&'\\');
+                        break;
+                    }
+                }
+            },
+            Some(' ' ..= '/') => {
+                while chars.next_if(|character| (' ' ..= '/').contains(character)).is_some() {}
+                chars.next();
+            },
+            _ => {},
+        }
+    }
+    (stripped, styles)
+}
+fn select_graphic_rendition(mut style: Style, parameters: &str) -> Style {
+    let mut codes = parameters.split([';', ':']).map(|code| code.parse::<u8>().unwrap_or(0));
+    while let Some(code) = codes.next() {
+        style = match code {
+            0 => Style::default(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            5 => style.add_modifier(Modifier::SLOW_BLINK),
+            6 => style.add_modifier(Modifier::RAPID_BLINK),
+            7 => style.add_modifier(Modifier::REVERSED),
+            8 => style.add_modifier(Modifier::HIDDEN),
+            9 => style.add_modifier(Modifier::CROSSED_OUT),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            25 => style.remove_modifier(Modifier::SLOW_BLINK | Modifier::RAPID_BLINK),
+            27 => style.remove_modifier(Modifier::REVERSED),
+            28 => style.remove_modifier(Modifier::HIDDEN),
+            29 => style.remove_modifier(Modifier::CROSSED_OUT),
+            30 ..= 37 => style.fg(Color::Indexed(code - 30)),
+            38 => extended_color(&mut codes).map_or(style, |color| style.fg(color)),
+            39 => style.fg(Color::Reset),
+            40 ..= 47 => style.bg(Color::Indexed(code - 40)),
+            48 => extended_color(&mut codes).map_or(style, |color| style.bg(color)),
+            49 => style.bg(Color::Reset),
+            90 ..= 97 => style.fg(Color::Indexed(code - 90 + 8)),
+            100 ..= 107 => style.bg(Color::Indexed(code - 100 + 8)),
+            _ => style,
+        };
+    }
+    style
+}
+fn extended_color(codes: &mut impl Iterator<Item = u8>) -> Option<Color> {
+    match codes.next()? {
+        5 => codes.next().map(Color::Indexed),
+        2 => Some(Color::Rgb(codes.next()?, codes.next()?, codes.next()?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_escapes_and_records_styles() {
+        let (text, styles) = strip("\x1b(B\x1b[1;31mré\x1b[0m d\x1b]8;;url\x07x\x1b]8;;\x1b\\");
+        let red = Style::default().add_modifier(Modifier::BOLD).fg(Color::Indexed(1));
+
+        assert_eq!(text, "ré dx");
+        assert_eq!(styles, [(0, red), (2, Style::default())]);
+    }
+    #[test]
+    fn reads_extended_colors() {
+        let (_, styles) = strip("\x1b[38;5;208;48;2;1;2;3mx");
+        let style = Style::default().fg(Color::Indexed(208)).bg(Color::Rgb(1, 2, 3));
+
+        assert_eq!(styles, [(0, style)]);
+    }
+}
@@ -0,0 +1,124 @@
+use std::{io, str::FromStr};
+
+use anyhow::{bail, Context, Error, Result};
+
+use super::{Header, Menu, MenuOption};
+
+#[derive(Clone, Copy)]
+struct FieldRange {
+    start: Option<isize>,
+    end: Option<isize>,
+}
+impl FieldRange {
+    fn indices(self, count: usize) -> impl Iterator<Item = usize> {
+        let count = isize::try_from(count).unwrap();
+        let resolve = |field: isize| if field < 0 { count + field } else { field - 1 };
+        let start = self.start.map_or(0, resolve).max(0);
+        let end = self.end.map_or(count - 1, resolve).min(count - 1);
+        (start ..= end).map(|index| usize::try_from(index).unwrap())
+    }
+}
+impl FromStr for FieldRange {
+    type Err = Error;
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let field = |text: &str| -> Result<Option<isize>> {
+            if text.is_empty() {
+                return Ok(None);
+            }
+            match text.parse() {
+                Ok(0) | Err(_) => bail!("Invalid field \"{text}\"."),
+                Ok(field) => Ok(Some(field)),
+            }
+        };
+        match text.split_once("..") {
+            Some((start, end)) => Ok(Self { start: field(start)?, end: field(end)? }),
+            None if text.is_empty() => bail!("Expected a field."),
+            None => {
+                let field = field(text)?;
+                Ok(Self { start: field, end: field })
+            },
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Fields(Vec<FieldRange>);
+impl Fields {
+    fn select<'f>(&self, fields: &[&'f str]) -> Vec<&'f str> {
+        let indices = self.0.iter().flat_map(|range| range.indices(fields.len()));
+        indices.map(|index| fields[index]).collect()
+    }
+}
+impl FromStr for Fields {
+    type Err = Error;
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        text.split(',').map(str::parse).collect::<Result<_>>().map(Self)
+    }
+}
+
+pub struct Columns {
+    pub delimiter: Option<String>,
+    pub display: Option<Fields>,
+    pub output: Option<Fields>,
+}
+impl Columns {
+    fn split<'l>(&self, line: &'l str) -> Vec<&'l str> {
+        self.delimiter.as_ref().map_or_else(
+            || line.split_whitespace().collect(),
+            |delimiter| line.split(delimiter.as_str()).collect(),
+        )
+    }
+    pub fn option(&self, line: &str) -> MenuOption<'static, 'static> {
+        let fields = self.split(line);
+        let select = |range: &Option<Fields>| {
+            range.as_ref().map_or_else(|| fields.clone(), |range| range.select(&fields))
+        };
+        let output = select(&self.output).join(self.delimiter.as_deref().unwrap_or(" "));
+        let columns = select(&self.display).into_iter().map(String::from).collect();
+
+        MenuOption::tabular(output, columns)
+    }
+}
+
+pub fn parse_table(
+    lines: impl Iterator<Item = io::Result<String>>,
+    source: &str,
+    columns: &Columns,
+) -> Result<Menu> {
+    let mut options = Vec::new();
+    for (index, line) in lines.enumerate() {
+        let number = index + 1;
+        let line = line.with_context(|| format!("Reading line {number} from {source} failed."))?;
+        if !line.trim().is_empty() {
+            options.push(columns.option(&line));
+        }
+    }
+    Menu::new(Header::default(), options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indices(range: &str, count: usize) -> Vec<usize> {
+        range.parse::<FieldRange>().unwrap().indices(count).collect()
+    }
+
+    #[test]
+    fn resolves_field_ranges() {
+        assert_eq!(indices("2", 4), [1]);
+        assert_eq!(indices("2..", 4), [1, 2, 3]);
+        assert_eq!(indices("..2", 4), [0, 1]);
+        assert_eq!(indices("-1", 4), [3]);
+        assert_eq!(indices("-2..", 4), [2, 3]);
+        assert_eq!(indices("..", 3), [0, 1, 2]);
+        assert!(indices("9", 4).is_empty());
+        assert!(indices("..", 0).is_empty());
+    }
+    #[test]
+    fn rejects_invalid_fields() {
+        for range in ["", "0", "a", "1..x", "0.."] {
+            assert!(range.parse::<FieldRange>().is_err(), "{range}");
+        }
+    }
+}
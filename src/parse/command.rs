@@ -0,0 +1,40 @@
+use std::process::{Command, Stdio};
+
+use anyhow::{anyhow, Context, Result};
+
+use super::{
+    columns::{parse_table, Columns},
+    from_reader,
+    records,
+    InputFormat,
+    Menu,
+};
+
+pub type Reload = Box<dyn Fn() -> Result<Menu>>;
+
+pub fn from_command(
+    command: &str,
+    format: InputFormat,
+    columns: Option<&Columns>,
+    read0: bool,
+) -> Result<Menu> {
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .stdin(Stdio::null())
+        .output()
+        .with_context(|| format!("Running \"{command}\" failed."))?;
+    let status = output.status;
+    if !status.success() {
+        let errors = String::from_utf8_lossy(&output.stderr);
+        let reason = anyhow!("{}", format!("{status}. {}", errors.trim()).trim_end());
+        return Err(reason.context(format!("Running \"{command}\" failed.")));
+    }
+
+    let source = format!("`{command}`");
+    let reader = output.stdout.as_slice();
+    columns.map_or_else(
+        || from_reader(reader, &source, None, format, read0),
+        |columns| parse_table(records(reader, read0), &source, columns),
+    )
+}
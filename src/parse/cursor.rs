@@ -0,0 +1,39 @@
+use std::{iter::Peekable, str::Chars};
+
+use super::diagnostic::ParseError;
+
+#[derive(Clone)]
+pub struct Cursor<'l> {
+    chars: Peekable<Chars<'l>>,
+    column: usize,
+}
+impl<'l> Cursor<'l> {
+    pub fn new(line: &'l str) -> Self {
+        Self { chars: line.chars().peekable(), column: 0 }
+    }
+    pub fn next_if(&mut self, condition: impl FnOnce(&char) -> bool) -> Option<char> {
+        let character = self.chars.next_if(condition)?;
+        self.column += 1;
+        Some(character)
+    }
+    pub fn next_if_eq(&mut self, expected: char) -> Option<char> {
+        self.next_if(|character| *character == expected)
+    }
+    pub fn skip_whitespace(&mut self) {
+        while self.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError { column: self.column.max(1), message: message.into() }
+    }
+    pub fn error_ahead(&self, message: impl Into<String>) -> ParseError {
+        ParseError { column: self.column + 1, message: message.into() }
+    }
+}
+impl Iterator for Cursor<'_> {
+    type Item = char;
+    fn next(&mut self) -> Option<Self::Item> {
+        let character = self.chars.next()?;
+        self.column += 1;
+        Some(character)
+    }
+}
@@ -0,0 +1,86 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+#[derive(Debug)]
+pub struct ParseError {
+    pub column: usize,
+    pub message: String,
+}
+impl ParseError {
+    pub fn at(column: usize, error: impl Display) -> Self {
+        Self { column, message: error.to_string() }
+    }
+    pub fn shifted(self, offset: usize) -> Self {
+        Self { column: self.column + offset, ..self }
+    }
+}
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (column {})", self.message, self.column)
+    }
+}
+impl Error for ParseError {}
+
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub source: String,
+    pub line: usize,
+    pub text: String,
+    pub error: ParseError,
+}
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Self { source, line, text, error: ParseError { column, message } } = self;
+        let gutter = " ".repeat(line.to_string().len());
+        let offset = " ".repeat(column - 1);
+
+        writeln!(f, "{message}")?;
+        writeln!(f, "{gutter}--> {source}:{line}:{column}")?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{line} | {text}")?;
+        writeln!(f, "{gutter} | {offset}^")
+    }
+}
+
+impl Diagnostic {
+    pub fn summary(&self) -> String {
+        let Self { source, line, error: ParseError { column, message }, .. } = self;
+        format!("{source}:{line}:{column}: {message}")
+    }
+    pub fn deserializing(
+        source: &str,
+        (line, column): (usize, usize),
+        text: &str,
+        path: &serde_path_to_error::Path,
+        error: impl Display,
+    ) -> Self {
+        let message = error.to_string();
+        let without_location = |message: &str, suffix| {
+            message.rsplit_once(suffix).map_or(message, |(message, _)| message).to_owned()
+        };
+        let message = without_location(&without_location(&message, " at line "), " for key ");
+        let message = match path.to_string().as_str() {
+            "." => message,
+            path => format!("{path}: {message}"),
+        };
+        let error = ParseError { column: column.max(1), message };
+
+        Self { source: String::from(source), line, text: String::from(text), error }
+    }
+}
+
+#[derive(Debug)]
+pub struct Diagnostics(pub Vec<Diagnostic>);
+impl Display for Diagnostics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Self(diagnostics) = self;
+        for diagnostic in diagnostics {
+            writeln!(f, "{diagnostic}")?;
+        }
+        let count = diagnostics.len();
+        write!(f, "{count} line{} failed to parse.", if count == 1 { "" } else { "s" })
+    }
+}
+impl Error for Diagnostics {}
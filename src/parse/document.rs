@@ -0,0 +1,70 @@
+use std::{fs, path::Path};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::{
+    diagnostic::{Diagnostic, Diagnostics},
+    record::OptionRecord,
+    Header,
+    Menu,
+    MenuOption,
+};
+use crate::{args::Color, interface::ui::customizations::BorderStyle};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Document {
+    title: Option<String>,
+    prompt: Option<String>,
+    border_style: Option<BorderStyle>,
+    #[serde(default)]
+    colors: Colors,
+    options: Vec<OptionRecord>,
+}
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Colors {
+    selected_fg: Option<Color>,
+    selected_bg: Option<Color>,
+    key_fg: Option<Color>,
+    key_bg: Option<Color>,
+    border: Option<Color>,
+}
+impl From<Document> for Menu {
+    fn from(document: Document) -> Self {
+        let Document { title, prompt, border_style, colors, options } = document;
+        let Colors { selected_fg, selected_bg, key_fg, key_bg, border } = colors;
+        let header = Header {
+            title,
+            prompt,
+            border_style,
+            color_selected_fg: selected_fg,
+            color_selected_bg: selected_bg,
+            color_key_fg: key_fg,
+            color_key_bg: key_bg,
+            color_border: border,
+        };
+        let options = options.into_iter().map(MenuOption::from).collect();
+
+        Self { header, options }
+    }
+}
+
+pub fn from_toml(path: &Path) -> Result<Menu> {
+    let source = path.display().to_string();
+    let text = fs::read_to_string(path).with_context(|| format!("Reading \"{source}\" failed."))?;
+
+    let deserializer = &mut toml::Deserializer::new(&text);
+    let document: Document = serde_path_to_error::deserialize(deserializer).map_err(|error| {
+        let position =
+            error.inner().line_col().map_or((1, 1), |(line, column)| (line + 1, column + 1));
+        let text = text.lines().nth(position.0 - 1).unwrap_or_default();
+        let diagnostic =
+            Diagnostic::deserializing(&source, position, text, error.path(), error.inner());
+        Diagnostics(vec![diagnostic])
+    })?;
+
+    let Menu { header, options } = Menu::from(document);
+    Menu::new(header, options.into_vec())
+}
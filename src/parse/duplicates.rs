@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+
+use super::{
+    hotkey::{DuplicateKeys, Hotkey, KeyCase, KeyPolicy},
+    MenuOption,
+};
+
+pub fn check_duplicates(options: &[MenuOption], policy: KeyPolicy) -> Result<()> {
+    let mut duplicates = Vec::new();
+    find_duplicates(options, policy.case, &mut duplicates);
+
+    match policy.duplicates {
+        DuplicateKeys::Error if !duplicates.is_empty() => bail!(duplicates.join("\n")),
+        DuplicateKeys::Warn => {
+            for duplicate in duplicates {
+                eprintln!("Warning: {duplicate}");
+            }
+        },
+        _ => {},
+    }
+    Ok(())
+}
+pub fn conflicts(options: &[MenuOption], case: KeyCase) -> Box<[bool]> {
+    let groups = group_by_key(options, case);
+    options
+        .iter()
+        .map(|option| groups.get(&option.key.folded(case)).is_some_and(|group| group.len() > 1))
+        .collect()
+}
+fn find_duplicates(options: &[MenuOption], case: KeyCase, duplicates: &mut Vec<String>) {
+    let groups = group_by_key(options, case);
+    let mut reported = Vec::new();
+    for option in options {
+        let key = option.key.folded(case);
+        match groups.get(&key) {
+            Some(group) if group.len() > 1 && !reported.contains(&key) => {
+                let displays = group.iter().map(|option| describe(option));
+                let displays = displays.collect::<Vec<_>>().join(", ");
+                duplicates.push(format!("Hotkey \"{}\" is bound to {displays}.", option.key));
+                reported.push(key);
+            },
+            _ => {},
+        }
+        find_duplicates(&option.children, case, duplicates);
+    }
+}
+fn describe(option: &MenuOption) -> String {
+    let origin = option.origin.as_ref().map(|origin| format!(" ({origin})")).unwrap_or_default();
+    format!("\"{}\"{origin}", option.display)
+}
+fn group_by_key<'o, 'm>(
+    options: &'o [MenuOption<'m, 'm>],
+    case: KeyCase,
+) -> HashMap<Hotkey, Vec<&'o MenuOption<'m, 'm>>> {
+    let mut groups = HashMap::<_, Vec<_>>::new();
+    for option in options.iter().filter(|option| option.is_selectable() && !option.key.is_empty()) {
+        groups.entry(option.key.folded(case)).or_default().push(option);
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(lines: &[&str]) -> Vec<MenuOption<'static, 'static>> {
+        lines.iter().map(|line| line.parse().unwrap()).collect()
+    }
+    fn duplicates(options: &[MenuOption], case: KeyCase) -> Vec<String> {
+        let mut duplicates = Vec::new();
+        find_duplicates(options, case, &mut duplicates);
+        duplicates
+    }
+
+    #[test]
+    fn reports_each_duplicated_key_once() {
+        let options = options(&["a : x", "A : y", "a : z", "b : w"]);
+
+        assert_eq!(
+            duplicates(&options, KeyCase::Sensitive),
+            [r#"Hotkey "a" is bound to "x", "z"."#]
+        );
+        assert_eq!(
+            duplicates(&options, KeyCase::Insensitive),
+            [r#"Hotkey "a" is bound to "x", "y", "z"."#]
+        );
+        assert_eq!(*conflicts(&options, KeyCase::Sensitive), [true, false, true, false]);
+    }
+    #[test]
+    fn checks_each_submenu_on_its_own() {
+        let mut options = options(&["a : x", ": y", ": z", "--- a", "--- a"]);
+        options[0].children = self::options(&["a : x", "b : y", "b : z"]).into_boxed_slice();
+
+        assert_eq!(
+            duplicates(&options, KeyCase::Sensitive),
+            [r#"Hotkey "b" is bound to "y", "z"."#]
+        );
+    }
+    #[test]
+    fn fails_only_with_the_error_policy() {
+        let options = options(&["a : x", "a : y"]);
+        let policy = |duplicates| KeyPolicy { case: KeyCase::Sensitive, duplicates, auto: false };
+
+        assert!(check_duplicates(&options, policy(DuplicateKeys::Error)).is_err());
+        assert!(check_duplicates(&options, policy(DuplicateKeys::First)).is_ok());
+        assert!(check_duplicates(&options, policy(DuplicateKeys::Cycle)).is_ok());
+    }
+}
@@ -0,0 +1,79 @@
+use super::{cursor::Cursor, diagnostic::ParseError};
+
+pub fn parse_field(
+    chars: &mut Cursor,
+    separator: Option<char>,
+) -> Result<(String, bool), ParseError> {
+    let mut lookahead = chars.clone();
+    lookahead.skip_whitespace();
+    if lookahead.next_if_eq('"').is_some() {
+        *chars = lookahead;
+        let field = parse_quoted(chars)?;
+        chars.skip_whitespace();
+        match (chars.next(), separator) {
+            (None, _) => Ok((field, false)),
+            (Some(found), Some(separator)) if found == separator => Ok((field, true)),
+            (Some(found), _) => {
+                Err(chars.error(format!("Unexpected \"{found}\" after closing quote.")))
+            },
+        }
+    }
+    else {
+        let mut field = String::new();
+        while let Some(character) = chars.next() {
+            match character {
+                '\\' => field.push_str(&unescape(chars)),
+                _ if Some(character) == separator => return Ok((field, true)),
+                _ => field.push(character),
+            }
+        }
+        Ok((field, false))
+    }
+}
+fn parse_quoted(chars: &mut Cursor) -> Result<String, ParseError> {
+    let mut field = String::new();
+    while let Some(character) = chars.next() {
+        match character {
+            '"' => return Ok(field),
+            '\\' => field.push_str(&unescape(chars)),
+            _ => field.push(character),
+        }
+    }
+    Err(chars.error_ahead("Expected a closing quote."))
+}
+fn unescape(chars: &mut Cursor) -> String {
+    match chars.next() {
+        Some('n') => String::from('\n'),
+        Some('t') => String::from('\t'),
+        Some(character @ ('\\' | '|' | '"')) => String::from(character),
+        Some(other) => format!("\\{other}"),
+        None => String::from('\\'),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(text: &str, separator: Option<char>) -> Result<(String, bool), ParseError> {
+        parse_field(&mut Cursor::new(text), separator)
+    }
+
+    #[test]
+    fn reads_up_to_the_separator() {
+        assert_eq!(field("ls -l|List", Some('|')).unwrap(), (String::from("ls -l"), true));
+        assert_eq!(field("grüße", Some('|')).unwrap(), (String::from("grüße"), false));
+        assert_eq!(field(r"a\|b|c", Some('|')).unwrap(), (String::from("a|b"), true));
+        assert_eq!(field(r"a\tb\x", None).unwrap(), (String::from("a\tb\\x"), false));
+    }
+    #[test]
+    fn reads_quoted_fields() {
+        assert_eq!(field(r#" "a | b" | c"#, Some('|')).unwrap(), (String::from("a | b"), true));
+        assert_eq!(field(r#""日本\"語""#, None).unwrap(), (String::from("日本\"語"), false));
+    }
+    #[test]
+    fn reports_character_columns() {
+        assert_eq!(field(r#""日本" x"#, None).unwrap_err().column, 6);
+        assert_eq!(field(r#""日本"#, None).unwrap_err().column, 4);
+    }
+}
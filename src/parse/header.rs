@@ -0,0 +1,73 @@
+use clap::ValueEnum;
+
+use super::diagnostic::ParseError;
+use crate::{args::Color, interface::ui::customizations::BorderStyle};
+
+pub const DELIMITER: &str = "---";
+
+pub fn is_setting(line: &str) -> bool {
+    let in_name = |c: char| c.is_alphanumeric() || c == '-';
+    let setting = |(name, _): (&str, _)| !name.is_empty() && name.chars().all(in_name);
+    line.trim_start().split_once(':').is_some_and(setting)
+}
+
+#[derive(Default)]
+pub struct Header {
+    pub title: Option<String>,
+    pub prompt: Option<String>,
+    pub border_style: Option<BorderStyle>,
+    pub color_selected_fg: Option<Color>,
+    pub color_selected_bg: Option<Color>,
+    pub color_key_fg: Option<Color>,
+    pub color_key_bg: Option<Color>,
+    pub color_border: Option<Color>,
+}
+impl Header {
+    pub fn merge(&mut self, other: Self) {
+        let Self {
+            title,
+            prompt,
+            border_style,
+            color_selected_fg,
+            color_selected_bg,
+            color_key_fg,
+            color_key_bg,
+            color_border,
+        } = other;
+        self.title = self.title.take().or(title);
+        self.prompt = self.prompt.take().or(prompt);
+        self.border_style = self.border_style.take().or(border_style);
+        self.color_selected_fg = self.color_selected_fg.take().or(color_selected_fg);
+        self.color_selected_bg = self.color_selected_bg.take().or(color_selected_bg);
+        self.color_key_fg = self.color_key_fg.take().or(color_key_fg);
+        self.color_key_bg = self.color_key_bg.take().or(color_key_bg);
+        self.color_border = self.color_border.take().or(color_border);
+    }
+    pub fn parse_line(&mut self, line: &str) -> Result<(), ParseError> {
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| ParseError::at(1, "Expected a \"name: value\" setting."))?;
+        let value_column =
+            name.chars().count() + 2 + value.chars().take_while(|c| c.is_whitespace()).count();
+        let (name, value) = (name.trim(), value.trim());
+
+        let invalid = |kind| ParseError::at(value_column, format!("Invalid {kind} \"{value}\"."));
+        let color = || value.parse().map_err(|_| invalid("color"));
+        match name {
+            "title" => self.title = Some(String::from(value)),
+            "prompt" => self.prompt = Some(String::from(value)),
+            "border-style" => {
+                let style =
+                    BorderStyle::from_str(value, true).map_err(|_| invalid("border style"))?;
+                self.border_style = Some(style);
+            },
+            "color-selected-fg" => self.color_selected_fg = Some(color()?),
+            "color-selected-bg" => self.color_selected_bg = Some(color()?),
+            "color-key-fg" => self.color_key_fg = Some(color()?),
+            "color-key-bg" => self.color_key_bg = Some(color()?),
+            "color-border" => self.color_border = Some(color()?),
+            other => return Err(ParseError::at(1, format!("Unknown setting \"{other}\"."))),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,293 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail, ensure, Error, Result};
+use clap::ValueEnum;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{de, Deserialize, Deserializer};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum KeyCase {
+    Sensitive,
+    Insensitive,
+}
+impl KeyCase {
+    pub fn fold(self, key: Key) -> Key {
+        match (self, key.code) {
+            (Self::Insensitive, KeyCode::Char(character)) => {
+                Key { code: KeyCode::Char(character.to_lowercase().next().unwrap()), ..key }
+            },
+            _ => key,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DuplicateKeys {
+    Error,
+    Warn,
+    First,
+    Cycle,
+}
+impl DuplicateKeys {
+    pub const fn is_reported(self) -> bool {
+        matches!(self, Self::Error | Self::Warn)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct KeyPolicy {
+    pub case: KeyCase,
+    pub duplicates: DuplicateKeys,
+    pub auto: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+impl Key {
+    pub const fn plain(character: char) -> Self {
+        Self { code: KeyCode::Char(character), modifiers: KeyModifiers::NONE }
+    }
+    fn normalized(self) -> Self {
+        let Self { code, mut modifiers } = self;
+        if !modifiers.contains(KeyModifiers::SHIFT) {
+            return self;
+        }
+
+        let code = match code {
+            KeyCode::Char(character) => {
+                modifiers.remove(KeyModifiers::SHIFT);
+                let mut uppercase = character.to_uppercase();
+                match (uppercase.next(), uppercase.next()) {
+                    (Some(upper), None) => KeyCode::Char(upper),
+                    _ => code,
+                }
+            },
+            KeyCode::BackTab => KeyCode::Tab,
+            _ => code,
+        };
+        Self { code, modifiers }
+    }
+    const fn is_plain(self) -> bool {
+        matches!(self.code, KeyCode::Char(character) if character != ' ')
+            && self.modifiers.is_empty()
+    }
+    fn parse_code(name: &str) -> Result<KeyCode> {
+        let mut characters = name.chars();
+        if let (Some(character), None) = (characters.next(), characters.next()) {
+            return Ok(KeyCode::Char(character));
+        }
+
+        Ok(match name.to_lowercase().as_str() {
+            "space" => KeyCode::Char(' '),
+            "lt" => KeyCode::Char('<'),
+            "gt" => KeyCode::Char('>'),
+            "tab" => KeyCode::Tab,
+            "enter" | "return" | "cr" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "bs" | "backspace" => KeyCode::Backspace,
+            "del" | "delete" => KeyCode::Delete,
+            "ins" | "insert" => KeyCode::Insert,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            other => {
+                let number = other
+                    .strip_prefix('f')
+                    .and_then(|number| number.parse::<u8>().ok())
+                    .ok_or_else(|| anyhow!("Unknown key name \"{name}\"."))?;
+                ensure!((1 ..= 24).contains(&number), "Function key \"{name}\" does not exist.");
+                KeyCode::F(number)
+            },
+        })
+    }
+}
+impl From<KeyEvent> for Key {
+    fn from(event: KeyEvent) -> Self {
+        Self { code: event.code, modifiers: event.modifiers }.normalized()
+    }
+}
+impl FromStr for Key {
+    type Err = Error;
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = spec;
+        while let Some((modifier, name)) = rest.split_once('-').filter(|(_, name)| !name.is_empty())
+        {
+            modifiers |= match modifier {
+                "C" | "c" => KeyModifiers::CONTROL,
+                "M" | "m" | "A" => KeyModifiers::ALT,
+                "S" | "s" => KeyModifiers::SHIFT,
+                _ => bail!("Unknown modifier \"{modifier}\" in key \"<{spec}>\"."),
+            };
+            rest = name;
+        }
+        let code = Self::parse_code(rest)?;
+
+        Ok(Self { code, modifiers }.normalized())
+    }
+}
+impl Display for Key {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift+")?;
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(character) => write!(f, "{character}"),
+            KeyCode::F(number) => write!(f, "F{number}"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::Enter => write!(f, "Enter"),
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Backspace => write!(f, "Backspace"),
+            KeyCode::Delete => write!(f, "Del"),
+            KeyCode::Insert => write!(f, "Ins"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::Up => write!(f, "Up"),
+            KeyCode::Down => write!(f, "Down"),
+            KeyCode::Left => write!(f, "Left"),
+            KeyCode::Right => write!(f, "Right"),
+            _ => write!(f, "?"),
+        }
+    }
+}
+
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct Hotkey(Vec<Key>);
+impl Hotkey {
+    pub fn folded(&self, case: KeyCase) -> Self {
+        self.0.iter().map(|&key| case.fold(key)).collect()
+    }
+    pub fn starts_with(&self, prefix: &Self, case: KeyCase) -> bool {
+        let same = |(key, other): (&Key, &Key)| case.fold(*key) == case.fold(*other);
+        self.0.len() >= prefix.0.len() && self.0.iter().zip(&prefix.0).all(same)
+    }
+    pub fn matches(&self, other: &Self, case: KeyCase) -> bool {
+        self.0.len() == other.0.len() && self.starts_with(other, case)
+    }
+    pub fn push(&mut self, key: Key) {
+        self.0.push(key);
+    }
+    pub fn pop(&mut self) {
+        self.0.pop();
+    }
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn first(&self) -> Option<Key> {
+        self.0.first().copied()
+    }
+}
+impl FromIterator<Key> for Hotkey {
+    fn from_iter<I: IntoIterator<Item = Key>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+impl FromStr for Hotkey {
+    type Err = Error;
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut keys = Vec::new();
+        let mut rest = text;
+        while let Some(character) = rest.chars().next() {
+            let spec = rest
+                .strip_prefix('<')
+                .and_then(|inner| inner.split_once('>'))
+                .filter(|(spec, _)| !spec.is_empty());
+            if let Some((spec, after)) = spec {
+                keys.push(spec.parse()?);
+                rest = after;
+            }
+            else {
+                keys.push(Key::plain(character));
+                rest = &rest[character.len_utf8() ..];
+            }
+        }
+        ensure!(!keys.is_empty(), "Expected a key.");
+
+        Ok(Self(keys))
+    }
+}
+impl<'de> Deserialize<'de> for Hotkey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        text.parse().map_err(de::Error::custom)
+    }
+}
+impl Display for Hotkey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut previous: Option<Key> = None;
+        for &key in &self.0 {
+            if previous.is_some_and(|previous| !previous.is_plain() || !key.is_plain()) {
+                write!(f, " ")?;
+            }
+            write!(f, "{key}")?;
+            previous = Some(key);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hotkey(text: &str) -> String {
+        text.parse::<Hotkey>().unwrap().to_string()
+    }
+
+    #[test]
+    fn parses_characters_and_key_specs() {
+        assert_eq!(hotkey("ab"), "ab");
+        assert_eq!(hotkey("é日"), "é日");
+        assert_eq!(hotkey("<C-a>"), "Ctrl+a");
+        assert_eq!(hotkey("g<F5>"), "g F5");
+        assert_eq!(hotkey("<space>"), "Space");
+        assert_eq!(hotkey("<lt>"), "<");
+        assert_eq!(hotkey("<:>"), ":");
+        assert_eq!(hotkey("<C-:>"), "Ctrl+:");
+        assert_eq!(hotkey("<>"), "<>");
+    }
+    #[test]
+    fn rejects_invalid_specs() {
+        for text in ["", "<Foo>", "<X-a>", "<F25>"] {
+            assert!(text.parse::<Hotkey>().is_err(), "{text}");
+        }
+    }
+    #[test]
+    fn normalizes_shift_on_characters() {
+        let event = KeyEvent::new(KeyCode::Char('A'), KeyModifiers::SHIFT);
+        assert!(Key::from(event) == Key::plain('A'));
+        assert_eq!(hotkey("<S-a>"), "A");
+    }
+    #[test]
+    fn folds_case_when_insensitive() {
+        let upper = "gC".parse::<Hotkey>().unwrap();
+        let lower = "gc".parse::<Hotkey>().unwrap();
+        assert!(!upper.matches(&lower, KeyCase::Sensitive));
+        assert!(upper.matches(&lower, KeyCase::Insensitive));
+        assert!(upper.starts_with(&"G".parse().unwrap(), KeyCase::Insensitive));
+    }
+}
@@ -0,0 +1,72 @@
+use std::io;
+
+use anyhow::{ensure, Context, Result};
+
+use super::{
+    diagnostic::{Diagnostic, Diagnostics},
+    record::OptionRecord,
+    Header,
+    Menu,
+    MenuOption,
+};
+
+pub fn parse_json(text: &str, source: &str) -> Result<Menu> {
+    let deserializer = &mut serde_json::Deserializer::from_str(text);
+    let options = serde_path_to_error::deserialize::<_, Vec<OptionRecord>>(deserializer).map_err(
+        |error| {
+            let line = error.inner().line();
+            let text = text.lines().nth(line.saturating_sub(1)).unwrap_or_default();
+            Diagnostics(vec![diagnose(&error, source, line, text)])
+        },
+    )?;
+
+    Menu::new(Header::default(), options.into_iter().map(MenuOption::from).collect())
+}
+pub fn parse_json_lines(
+    lines: impl Iterator<Item = io::Result<String>>,
+    source: &str,
+) -> Result<Menu> {
+    let mut options = Vec::new();
+    let mut diagnostics = Vec::new();
+    stream_json_lines(lines, source, |parsed| {
+        match parsed {
+            Ok(option) => options.push(option),
+            Err(diagnostic) => diagnostics.push(diagnostic),
+        }
+        true
+    })?;
+
+    ensure!(diagnostics.is_empty(), Diagnostics(diagnostics));
+    Menu::new(Header::default(), options)
+}
+pub fn stream_json_lines(
+    lines: impl Iterator<Item = io::Result<String>>,
+    source: &str,
+    mut emit: impl FnMut(Result<MenuOption<'static, 'static>, Diagnostic>) -> bool,
+) -> Result<()> {
+    for (index, line) in lines.enumerate() {
+        let number = index + 1;
+        let line = line.with_context(|| format!("Reading line {number} from {source} failed."))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let deserializer = &mut serde_json::Deserializer::from_str(&line);
+        let parsed = serde_path_to_error::deserialize::<_, OptionRecord>(deserializer)
+            .map(MenuOption::from)
+            .map_err(|error| diagnose(&error, source, number, &line));
+        if !emit(parsed) {
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+fn diagnose(
+    error: &serde_path_to_error::Error<serde_json::Error>,
+    source: &str,
+    line: usize,
+    text: &str,
+) -> Diagnostic {
+    let position = (line, error.inner().column());
+    Diagnostic::deserializing(source, position, text, error.path(), error.inner())
+}
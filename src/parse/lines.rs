@@ -0,0 +1,319 @@
+use std::{
+    io,
+    mem,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{ensure, Context, Result};
+
+use super::{
+    diagnostic::{Diagnostic, Diagnostics, ParseError},
+    header::{self, Header},
+    open,
+    records,
+    tree::TreeBuilder,
+    Menu,
+    MenuOption,
+};
+
+const INCLUDE: &str = "@include";
+
+pub struct LineParser {
+    read0: bool,
+    header: Header,
+    tree: TreeBuilder,
+    diagnostics: Vec<Diagnostic>,
+    including: Vec<PathBuf>,
+}
+impl LineParser {
+    pub fn new(read0: bool) -> Self {
+        let header = Header::default();
+        let tree = TreeBuilder::default();
+        let diagnostics = Vec::new();
+        let including = Vec::new();
+
+        Self { read0, header, tree, diagnostics, including }
+    }
+    pub fn parse(
+        &mut self,
+        lines: impl Iterator<Item = io::Result<String>>,
+        source: &str,
+        path: Option<&Path>,
+        indent: usize,
+    ) -> Result<()> {
+        if let Some(path) = path {
+            let canonical = path
+                .canonicalize()
+                .with_context(|| format!("Resolving \"{}\" failed.", path.display()))?;
+            let cycle = self.including.contains(&canonical);
+            ensure!(!cycle, "Including \"{}\" forms a cycle.", path.display());
+            self.including.push(canonical);
+        }
+
+        let directory = path.and_then(Path::parent).unwrap_or_else(|| Path::new(""));
+        let parsed = self.parse_lines(lines, source, directory, indent);
+        if path.is_some() {
+            self.including.pop();
+        }
+        parsed
+    }
+    pub fn finish(self) -> Result<Menu> {
+        if self.diagnostics.is_empty() {
+            Menu::new(self.header, self.tree.finish())
+        }
+        else {
+            Err(Diagnostics(self.diagnostics).into())
+        }
+    }
+    pub fn stream(
+        mut self,
+        lines: impl Iterator<Item = io::Result<String>>,
+        source: &str,
+        mut emit: impl FnMut(Result<MenuOption<'static, 'static>, Diagnostic>) -> bool,
+    ) -> Result<()> {
+        for (index, line) in lines.enumerate() {
+            let number = index + 1;
+            let line =
+                line.with_context(|| format!("Reading line {number} from {source} failed."))?;
+            self.parse_line(number, line, source, Path::new(""), 0);
+            let failed = self.diagnostics.drain(..).map(Err);
+            if !failed.chain(self.tree.take_roots().into_iter().map(Ok)).all(&mut emit) {
+                return Ok(());
+            }
+        }
+
+        self.tree.finish().into_iter().map(Ok).all(emit);
+        Ok(())
+    }
+    fn parse_lines(
+        &mut self,
+        lines: impl Iterator<Item = io::Result<String>>,
+        source: &str,
+        directory: &Path,
+        base_indent: usize,
+    ) -> Result<()> {
+        let mut lines = lines.enumerate().map(|(index, line)| -> Result<(usize, String)> {
+            let number = index + 1;
+            let line =
+                line.with_context(|| format!("Reading line {number} from {source} failed."))?;
+            Ok((number, line))
+        });
+
+        let mut pending = Vec::new();
+        if let Some(first) = lines.next() {
+            let first = first?;
+            let opens = first.1.trim_end() == header::DELIMITER;
+            pending.push(first);
+            if opens {
+                for next in lines.by_ref() {
+                    let (number, line) = next?;
+                    let closes = line.trim_end() == header::DELIMITER;
+                    let setting = is_ignored(&line) || header::is_setting(&line);
+                    pending.push((number, line));
+                    if closes {
+                        self.parse_header(mem::take(&mut pending), source);
+                    }
+                    if closes || !setting {
+                        break;
+                    }
+                }
+            }
+        }
+
+        for next in pending.into_iter().map(Ok).chain(lines) {
+            let (number, line) = next?;
+            self.parse_line(number, line, source, directory, base_indent);
+        }
+        Ok(())
+    }
+    fn parse_line(
+        &mut self,
+        number: usize,
+        line: String,
+        source: &str,
+        directory: &Path,
+        base_indent: usize,
+    ) {
+        if is_ignored(&line) {
+            return;
+        }
+        let indent = line.chars().take_while(|c| c.is_whitespace()).count();
+        let origin = format!("{source}:{number}");
+        let parsed = self
+            .parse_entry(line.trim_start(), directory, base_indent + indent, origin)
+            .map_err(|error| error.shifted(indent));
+        if let Err(error) = parsed {
+            self.diagnose(source, number, line, error);
+        }
+    }
+    fn parse_header(&mut self, lines: Vec<(usize, String)>, source: &str) {
+        let mut header = Header::default();
+        let settings = lines.into_iter().filter(|(_, line)| header::is_setting(line));
+        for (number, line) in settings {
+            if let Err(error) = header.parse_line(&line) {
+                self.diagnose(source, number, line, error);
+            }
+        }
+        self.header.merge(header);
+    }
+    fn diagnose(&mut self, source: &str, line: usize, text: String, error: ParseError) {
+        let source = String::from(source);
+        self.diagnostics.push(Diagnostic { source, line, text, error });
+    }
+    fn parse_entry(
+        &mut self,
+        entry: &str,
+        directory: &Path,
+        indent: usize,
+        origin: String,
+    ) -> Result<(), ParseError> {
+        match entry.strip_prefix(INCLUDE) {
+            Some(included) if included.starts_with(char::is_whitespace) => {
+                let column = entry.chars().count() - included.trim_start().chars().count() + 1;
+                let path = directory.join(included.trim());
+                self.include(&path, indent).map_err(|error| {
+                    let reasons = error.chain().map(ToString::to_string).collect::<Vec<_>>();
+                    ParseError::at(column, reasons.join(" "))
+                })
+            },
+            _ => {
+                let option: MenuOption = entry.parse()?;
+                self.tree.push(indent, MenuOption { origin: Some(origin), ..option });
+                Ok(())
+            },
+        }
+    }
+    fn include(&mut self, path: &Path, indent: usize) -> Result<()> {
+        let (reader, source) = open(Some(path))?;
+        self.parse(records(reader, self.read0), &source, Some(path), indent)
+    }
+}
+
+fn is_ignored(line: &str) -> bool {
+    let line = line.trim_start();
+    line.is_empty() || line.starts_with('#')
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::*;
+    use crate::parse::Kind;
+
+    fn parse(text: &str) -> Result<Menu> {
+        let mut parser = LineParser::new(false);
+        parser.parse(text.lines().map(|line| Ok(String::from(line))), "test", None, 0)?;
+        parser.finish()
+    }
+    fn parse_file(path: &Path) -> Result<Menu> {
+        let mut parser = LineParser::new(false);
+        parser.include(path, 0)?;
+        parser.finish()
+    }
+    fn errors(menu: Result<Menu>) -> Vec<(usize, String)> {
+        let Diagnostics(diagnostics) = menu.err().unwrap().downcast().unwrap();
+        diagnostics
+            .into_iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.error.message))
+            .collect()
+    }
+    fn failed_lines(text: &str) -> Vec<usize> {
+        errors(parse(text)).into_iter().map(|(line, _)| line).collect()
+    }
+    fn directory(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let directory = env::temp_dir().join(format!("quick-menu-{name}-{}", process::id()));
+        fs::create_dir_all(&directory).unwrap();
+        for (file, text) in files {
+            fs::write(directory.join(file), text).unwrap();
+        }
+        directory
+    }
+    fn outline(options: &[MenuOption]) -> String {
+        let outline = |option: &MenuOption| match &*option.children {
+            [] => option.output.to_string(),
+            children => format!("{}({})", option.output, outline(children)),
+        };
+        options.iter().map(outline).collect::<Vec<_>>().join(" ")
+    }
+
+    #[test]
+    fn reads_the_header_and_skips_comments() {
+        let menu =
+            parse("---\ntitle: Git\n# comment\n---\n\na : add\n  # comment\nb : blame").unwrap();
+        assert_eq!(menu.header.title.as_deref(), Some("Git"));
+        assert_eq!(outline(&menu.options), "add blame");
+    }
+    #[test]
+    fn reads_a_header_only_when_it_is_closed() {
+        let kinds = |text| {
+            parse(text).unwrap().options.iter().map(|option| option.kind).collect::<Vec<_>>()
+        };
+        let (option, separator) = (Kind::Selectable, Kind::Separator);
+
+        assert!(kinds("---\ntitle: Git\n---\na : add") == [option]);
+        assert!(kinds("---\ntitle: Git\na : add") == [separator, option, option]);
+        assert!(kinds("---\na : add\n---\nb : blame") == [separator, option, separator, option]);
+    }
+    #[test]
+    fn reports_every_malformed_line() {
+        assert_eq!(failed_lines("---\ncolor-border: nope\n---\na : add"), [2]);
+        let unknown = errors(parse("---\ntitle: Git\ncolour: red\n---\na : add"));
+        assert_eq!(unknown, [(3, String::from("Unknown setting \"colour\"."))]);
+        assert_eq!(failed_lines("a : add\nb\nc : \"x\n---\nd : done"), [2, 3]);
+    }
+    #[test]
+    fn streams_diagnostics_as_soon_as_a_line_fails() {
+        let lines = ["a : add", "b", "c : commit"].map(|line| Ok(String::from(line)));
+        let mut streamed = Vec::new();
+        let stream = |parsed: Result<MenuOption, Diagnostic>| {
+            streamed.push(parsed.map_or_else(|error| error.summary(), |option| option.to_string()));
+            true
+        };
+        LineParser::new(false).stream(lines.into_iter(), "test", stream).unwrap();
+
+        assert_eq!(streamed, ["test:2:1: Expected a separator.", "add", "commit"]);
+    }
+    #[test]
+    fn reads_headings_and_separators() {
+        let menu = parse("a : add\n--- Git\nb : blame\n---\nc : commit").unwrap();
+        let kinds = menu.options.iter().map(|option| option.kind).collect::<Vec<_>>();
+        assert!(
+            kinds
+                == [
+                    Kind::Selectable,
+                    Kind::Heading,
+                    Kind::Selectable,
+                    Kind::Separator,
+                    Kind::Selectable
+                ]
+        );
+        assert_eq!(menu.options[1].display, "Git");
+    }
+    #[test]
+    fn indents_included_options_like_the_directive() {
+        let directory = directory(
+            "include",
+            &[
+                ("menu", "g : git\n  @include git\nq : quit"),
+                ("git", "c : commit\n  a : amend\np : push"),
+            ],
+        );
+        let menu = parse_file(&directory.join("menu")).unwrap();
+
+        assert_eq!(outline(&menu.options), "git(commit(amend) push) quit");
+        let origin = menu.options[0].children[1].origin.as_deref().unwrap();
+        assert!(origin.ends_with("git:3"), "{origin}");
+    }
+    #[test]
+    fn rejects_include_cycles() {
+        let directory =
+            directory("cycle", &[("a", "a : a\n@include b"), ("b", "b : b\n@include a")]);
+        let found = errors(parse_file(&directory.join("a")));
+
+        assert_eq!(found.len(), 1);
+        let (line, message) = &found[0];
+        assert_eq!(*line, 2);
+        assert!(message.contains("forms a cycle"), "{message}");
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::HashSet;
+
+use super::{
+    hotkey::{Hotkey, Key, KeyPolicy},
+    MenuOption,
+};
+
+const FALLBACK_KEYS: &str = "abcdefghijklmnopqrstuvwxyz0123456789";
+
+pub fn assign_keys(options: &mut [MenuOption], keys: KeyPolicy) {
+    let case = keys.case;
+    let first = |option: &MenuOption| option.key.first().map(|key| case.fold(key));
+    let mut taken = options.iter().filter_map(first).collect::<HashSet<_>>();
+
+    for option in options.iter_mut() {
+        assign_keys(&mut option.children, keys);
+        if !option.is_selectable() || !option.key.is_empty() {
+            continue;
+        }
+
+        let free = |character: &char| !taken.contains(&case.fold(Key::plain(*character)));
+        let lowercase = |character: char| character.to_lowercase().next().unwrap_or(character);
+        let mnemonic = option
+            .display
+            .chars()
+            .enumerate()
+            .map(|(index, character)| (index, lowercase(character)))
+            .find(|(_, character)| character.is_alphanumeric() && free(character));
+        let character =
+            mnemonic.map(|(_, character)| character).or_else(|| FALLBACK_KEYS.chars().find(free));
+
+        if let Some(character) = character {
+            taken.insert(case.fold(Key::plain(character)));
+            option.key = Hotkey::from_iter([Key::plain(character)]);
+            option.mnemonic = mnemonic
+                .map(|(index, _)| index)
+                .or_else(|| option.display.chars().position(|other| lowercase(other) == character));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::hotkey::{DuplicateKeys, KeyCase};
+
+    fn options(lines: &[&str]) -> Vec<MenuOption<'static, 'static>> {
+        lines.iter().map(|line| line.parse().unwrap()).collect()
+    }
+    const fn policy(case: KeyCase) -> KeyPolicy {
+        KeyPolicy { case, duplicates: DuplicateKeys::Error, auto: true }
+    }
+    fn keys(options: &[MenuOption]) -> Vec<(String, Option<usize>)> {
+        options.iter().map(|option| (option.key.to_string(), option.mnemonic)).collect()
+    }
+
+    #[test]
+    fn prefers_free_letters_of_the_display() {
+        let mut options = options(&["c : commit", ": Checkout", ": Push", ": pull", ": 123"]);
+        assign_keys(&mut options, policy(KeyCase::Sensitive));
+
+        let expected =
+            [("c", None), ("h", Some(1)), ("p", Some(0)), ("u", Some(1)), ("1", Some(0))];
+        assert_eq!(keys(&options), expected.map(|(key, mnemonic)| (String::from(key), mnemonic)));
+    }
+    #[test]
+    fn falls_back_to_free_characters() {
+        let mut options = options(&["a : x", "b : y", ": ab", ": --- -"]);
+        assign_keys(&mut options, policy(KeyCase::Sensitive));
+
+        assert_eq!(keys(&options)[2 ..], [(String::from("c"), None), (String::from("d"), None)]);
+    }
+    #[test]
+    fn assigns_submenus_separately() {
+        let mut parent = options(&["c : commit"]);
+        parent[0].children = options(&[": commit", "--- Commit"]).into_boxed_slice();
+        assign_keys(&mut parent, policy(KeyCase::Sensitive));
+
+        assert_eq!(
+            keys(&parent[0].children),
+            [(String::from("c"), Some(0)), (String::new(), None)]
+        );
+    }
+    #[test]
+    fn compares_keys_with_the_key_case() {
+        let mut sensitive = options(&["C : commit", ": checkout"]);
+        assign_keys(&mut sensitive, policy(KeyCase::Sensitive));
+        let mut insensitive = options(&["C : commit", ": checkout"]);
+        assign_keys(&mut insensitive, policy(KeyCase::Insensitive));
+
+        assert_eq!(keys(&sensitive)[1], (String::from("c"), Some(0)));
+        assert_eq!(keys(&insensitive)[1], (String::from("h"), Some(1)));
+    }
+}
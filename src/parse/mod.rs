@@ -0,0 +1,328 @@
+pub mod ansi;
+pub mod columns;
+pub mod command;
+pub mod cursor;
+pub mod diagnostic;
+pub mod document;
+pub mod duplicates;
+pub mod field;
+pub mod header;
+pub mod hotkey;
+pub mod json;
+pub mod lines;
+pub mod mnemonic;
+pub mod record;
+pub mod stream;
+pub mod tree;
+
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+    fs::File,
+    io::{self, stdin, BufRead, BufReader, ErrorKind},
+    iter,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use columns::{parse_table, Columns};
+use cursor::Cursor;
+use diagnostic::ParseError;
+use field::parse_field;
+pub use header::Header;
+pub use hotkey::Hotkey;
+use lines::LineParser;
+use ratatui::style::Style;
+use serde::Deserialize;
+
+pub const SEPARATOR: &str = "---";
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    #[default]
+    Selectable,
+    Heading,
+    Separator,
+}
+
+#[derive(Clone)]
+pub struct MenuOption<'o, 'd> {
+    pub key: Hotkey,
+    pub output: Cow<'o, str>,
+    pub display: Cow<'d, str>,
+    pub children: Box<[Self]>,
+    pub kind: Kind,
+    pub mnemonic: Option<usize>,
+    pub columns: Box<[String]>,
+    pub origin: Option<String>,
+    pub styles: Box<[(usize, Style)]>,
+}
+impl<'o, 'd> MenuOption<'o, 'd> {
+    pub fn new(key: Hotkey, output: Cow<'o, str>, display: Cow<'d, str>, kind: Kind) -> Self {
+        let children = Box::default();
+        let mnemonic = None;
+        let columns = Box::default();
+        let origin = None;
+        let styles = Box::default();
+
+        Self { key, output, display, children, kind, mnemonic, columns, origin, styles }
+    }
+    fn unselectable(kind: Kind, display: &str) -> Self {
+        let display = Cow::Owned(String::from(display));
+        Self::new(Hotkey::default(), Cow::Borrowed(""), display, kind)
+    }
+    fn plain(line: String) -> Self {
+        let output = Cow::Owned(line.clone());
+        Self::new(Hotkey::default(), output, Cow::Owned(line), Kind::Selectable)
+    }
+    fn tabular(output: String, columns: Vec<String>) -> Self {
+        let display = Cow::Owned(columns.join(" "));
+        let option = Self::new(Hotkey::default(), Cow::Owned(output), display, Kind::Selectable);
+        Self { columns: columns.into_boxed_slice(), ..option }
+    }
+    pub fn is_selectable(&self) -> bool {
+        self.kind == Kind::Selectable
+    }
+}
+impl<'o, 'd> FromStr for MenuOption<'o, 'd> {
+    type Err = ParseError;
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        match line.trim_end().strip_prefix(SEPARATOR) {
+            Some("") => return Ok(Self::unselectable(Kind::Separator, "")),
+            Some(heading) if heading.starts_with(' ') => {
+                return Ok(Self::unselectable(Kind::Heading, heading.trim()));
+            },
+            _ => {},
+        }
+
+        let closes = |chars: &Cursor| {
+            let mut lookahead = chars.clone();
+            iter::from_fn(|| lookahead.next_if(|c| !c.is_whitespace())).any(|c| c == '>')
+        };
+        let mut chars = Cursor::new(line);
+
+        let mut lookahead = chars.clone();
+        lookahead.next_if_eq(':');
+        lookahead.skip_whitespace();
+        let keyless = line.starts_with(':') && lookahead.next_if_eq(':').is_none();
+
+        let key = if keyless {
+            Hotkey::default()
+        }
+        else {
+            let first = chars.next().ok_or_else(|| chars.error_ahead("Expected a key."))?;
+            let mut key = String::from(first);
+            let mut bracketed = first == '<' && closes(&chars);
+            while let Some(character) =
+                chars.next_if(|c| !c.is_whitespace() && (bracketed || c != &':'))
+            {
+                match character {
+                    '<' => bracketed = closes(&chars),
+                    '>' => bracketed = false,
+                    _ => {},
+                }
+                key.push(character);
+            }
+            key.parse().map_err(|error| ParseError::at(1, error))?
+        };
+
+        chars.skip_whitespace();
+        if chars.next() != Some(':') {
+            return Err(chars.error("Expected a separator."));
+        }
+        chars.skip_whitespace();
+
+        let (output, separated) = parse_field(&mut chars, Some('|'))?;
+        let display = if separated { parse_field(&mut chars, None)?.0 } else { output.clone() };
+        let (output, display) = (Cow::Owned(output), Cow::Owned(display));
+
+        Ok(Self::new(key, output, display, Kind::Selectable))
+    }
+}
+impl<'o, 'd> Display for MenuOption<'o, 'd> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Self {
+            key: _,
+            output: _,
+            display,
+            children: _,
+            kind: _,
+            mnemonic: _,
+            columns: _,
+            origin: _,
+            styles: _,
+        } = self;
+        write!(f, "{display}")
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum InputFormat {
+    Lines,
+    Json,
+    Jsonl,
+    Plain,
+}
+
+pub struct Menu {
+    pub header: Header,
+    pub options: Box<[MenuOption<'static, 'static>]>,
+}
+impl Menu {
+    pub fn new(header: Header, options: Vec<MenuOption<'static, 'static>>) -> Result<Self> {
+        if options.is_empty() {
+            Err(anyhow!("No options where given."))
+        }
+        else {
+            Ok(Self { header, options: options.into_boxed_slice() })
+        }
+    }
+    pub fn merge(self, other: Self) -> Self {
+        let Self { mut header, options } = self;
+        header.merge(other.header);
+        let options = options.into_vec().into_iter().chain(other.options.into_vec()).collect();
+        Self { header, options }
+    }
+}
+
+pub fn from_arguments(
+    arguments: &[String],
+    format: InputFormat,
+    columns: Option<&Columns>,
+) -> Result<Menu> {
+    let source = "arguments";
+    let lines = || arguments.iter().cloned().map(Ok);
+    match (format, columns) {
+        (_, Some(columns)) => parse_table(lines(), source, columns),
+        (InputFormat::Lines, None) => {
+            let mut parser = LineParser::new(false);
+            parser.parse(lines(), source, None, 0)?;
+            parser.finish()
+        },
+        (InputFormat::Json, None) => {
+            let mut documents = arguments.iter().map(|argument| json::parse_json(argument, source));
+            let first =
+                documents.next().unwrap_or_else(|| Menu::new(Header::default(), Vec::new()))?;
+            documents.try_fold(first, |menu, document| Ok(menu.merge(document?)))
+        },
+        (InputFormat::Jsonl, None) => json::parse_json_lines(lines(), source),
+        (InputFormat::Plain, None) => parse_plain(lines(), source),
+    }
+}
+pub fn from_files(paths: &[PathBuf], format: InputFormat, read0: bool) -> Result<Menu> {
+    combine(paths, |path| from_file(path, format, read0))
+}
+pub fn from_tables(paths: &[PathBuf], columns: &Columns, read0: bool) -> Result<Menu> {
+    combine(paths, |path| from_table(path, columns, read0))
+}
+fn combine(paths: &[PathBuf], load: impl Fn(Option<&Path>) -> Result<Menu>) -> Result<Menu> {
+    let Some((first, rest)) = paths.split_first()
+    else {
+        return load(None);
+    };
+    let first = load(Some(first))?;
+    rest.iter().try_fold(first, |menu, path| Ok(menu.merge(load(Some(path))?)))
+}
+fn from_file(path: Option<&Path>, format: InputFormat, read0: bool) -> Result<Menu> {
+    let (reader, source) = open(path)?;
+    from_reader(reader, &source, path, format, read0)
+}
+fn from_reader(
+    mut reader: impl BufRead,
+    source: &str,
+    path: Option<&Path>,
+    format: InputFormat,
+    read0: bool,
+) -> Result<Menu> {
+    match format {
+        InputFormat::Lines => {
+            let mut parser = LineParser::new(read0);
+            parser.parse(records(reader, read0), source, path, 0)?;
+            parser.finish()
+        },
+        InputFormat::Json => {
+            let mut text = String::new();
+            reader
+                .read_to_string(&mut text)
+                .with_context(|| format!("Reading from {source} failed."))?;
+            json::parse_json(&text, source)
+        },
+        InputFormat::Jsonl => json::parse_json_lines(records(reader, read0), source),
+        InputFormat::Plain => parse_plain(records(reader, read0), source),
+    }
+}
+fn from_table(path: Option<&Path>, columns: &Columns, read0: bool) -> Result<Menu> {
+    let (reader, source) = open(path)?;
+    parse_table(records(reader, read0), &source, columns)
+}
+fn records<'r>(
+    reader: impl BufRead + 'r,
+    read0: bool,
+) -> Box<dyn Iterator<Item = io::Result<String>> + 'r> {
+    if read0 {
+        let to_string = |record| {
+            String::from_utf8(record).map_err(|error| io::Error::new(ErrorKind::InvalidData, error))
+        };
+        Box::new(reader.split(b'\0').map(move |record| record.and_then(to_string)))
+    }
+    else {
+        Box::new(reader.lines())
+    }
+}
+fn open(path: Option<&Path>) -> Result<(Box<dyn BufRead>, String)> {
+    match path {
+        Some(path) => {
+            let file = File::open(path)
+                .with_context(|| format!("Opening \"{}\" failed.", path.display()))?;
+            Ok((Box::new(BufReader::new(file)), path.display().to_string()))
+        },
+        None => Ok((Box::new(stdin().lock()), String::from("stdin"))),
+    }
+}
+fn parse_plain(lines: impl Iterator<Item = io::Result<String>>, source: &str) -> Result<Menu> {
+    let mut options = Vec::new();
+    for (index, line) in lines.enumerate() {
+        let number = index + 1;
+        let line = line.with_context(|| format!("Reading line {number} from {source} failed."))?;
+        if !line.trim().is_empty() {
+            options.push(MenuOption::plain(line));
+        }
+    }
+    Menu::new(Header::default(), options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(line: &str) -> String {
+        line.parse::<MenuOption>().unwrap().key.to_string()
+    }
+
+    #[test]
+    fn reads_keys_up_to_the_separating_colon() {
+        assert_eq!(key("gc : git commit"), "gc");
+        assert_eq!(key("<:> : colon"), ":");
+        assert_eq!(key("<C-:>: command"), "Ctrl+:");
+        assert_eq!(key("a<lt>:less"), "a<");
+    }
+    #[test]
+    fn parses_arguments_with_the_input_format() {
+        let arguments = [String::from("foo : bar"), String::from("baz")];
+        let outputs = |format, columns: Option<&Columns>| {
+            let menu = from_arguments(&arguments, format, columns).unwrap();
+            menu.options.iter().map(|option| option.output.to_string()).collect::<Vec<_>>()
+        };
+        let columns = Columns { delimiter: Some(String::from(":")), display: None, output: None };
+
+        assert!(from_arguments(&arguments, InputFormat::Lines, None).is_err());
+        assert_eq!(outputs(InputFormat::Plain, None), ["foo : bar", "baz"]);
+        assert_eq!(outputs(InputFormat::Lines, Some(&columns)), ["foo : bar", "baz"]);
+        let records = [String::from(r#"[{"output": "a"}]"#), String::from(r#"[{"output": "b"}]"#)];
+        let menu = from_arguments(&records, InputFormat::Json, None).unwrap();
+        assert_eq!(menu.options.len(), 2);
+    }
+}
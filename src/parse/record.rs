@@ -0,0 +1,96 @@
+use std::borrow::Cow;
+
+use serde::Deserialize;
+
+use super::{Hotkey, Kind, MenuOption};
+
+#[derive(Deserialize)]
+#[serde(try_from = "RecordFields")]
+pub struct OptionRecord {
+    key: Hotkey,
+    output: String,
+    display: Option<String>,
+    children: Vec<Self>,
+    kind: Kind,
+}
+#[derive(Deserialize)]
+struct RecordFields {
+    #[serde(default)]
+    key: Hotkey,
+    output: Option<String>,
+    display: Option<String>,
+    #[serde(default)]
+    children: Vec<OptionRecord>,
+    #[serde(default)]
+    kind: Kind,
+}
+impl TryFrom<RecordFields> for OptionRecord {
+    type Error = String;
+
+    fn try_from(fields: RecordFields) -> Result<Self, Self::Error> {
+        let RecordFields { key, output, display, children, kind } = fields;
+        let name = match kind {
+            Kind::Selectable => "",
+            Kind::Heading => "heading",
+            Kind::Separator => "separator",
+        };
+        if !name.is_empty() && !key.is_empty() {
+            return Err(format!("A {name} can't have a key."));
+        }
+        if !name.is_empty() && !children.is_empty() {
+            return Err(format!("A {name} can't have children."));
+        }
+        let output = match (kind, output) {
+            (Kind::Selectable, None) => return Err(String::from("An option needs an output.")),
+            (_, output) => output.unwrap_or_default(),
+        };
+        Ok(Self { key, output, display, children, kind })
+    }
+}
+impl From<OptionRecord> for MenuOption<'static, 'static> {
+    fn from(option: OptionRecord) -> Self {
+        let OptionRecord { key, output, display, children, kind } = option;
+        let display = Cow::Owned(display.unwrap_or_else(|| output.clone()));
+        let output = Cow::Owned(output);
+        let children = children.into_iter().map(Self::from).collect();
+
+        Self { children, ..Self::new(key, output, display, kind) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(json: &str) -> MenuOption<'static, 'static> {
+        serde_json::from_str::<OptionRecord>(json).ok().unwrap().into()
+    }
+    fn error(json: &str) -> String {
+        serde_json::from_str::<OptionRecord>(json).err().unwrap().to_string()
+    }
+
+    #[test]
+    fn accepts_nested_options() {
+        let parent = option(r#"{"output":"git","children":[{"key":"p","output":"push"}]}"#);
+
+        assert_eq!(parent.display, "git");
+        assert_eq!(parent.children[0].display, "push");
+        assert_eq!(parent.children[0].key.to_string(), "p");
+    }
+    #[test]
+    fn rejects_keys_and_children_on_headings_and_separators() {
+        let heading = error(r#"{"kind":"heading","display":"Git","key":"g"}"#);
+        assert!(heading.starts_with("A heading can't have a key."), "{heading}");
+
+        let separator = error(r#"{"kind":"separator","children":[{"output":"x"}]}"#);
+        assert!(separator.starts_with("A separator can't have children."), "{separator}");
+    }
+    #[test]
+    fn requires_an_output_for_selectable_options() {
+        let missing = error(r#"{"key":"a","display":"Add"}"#);
+        assert!(missing.starts_with("An option needs an output."), "{missing}");
+
+        let heading = option(r#"{"kind":"heading","display":"Git"}"#);
+        assert_eq!(heading.output, "");
+    }
+}
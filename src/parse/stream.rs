@@ -0,0 +1,100 @@
+use std::{
+    io::{self, stdin, BufRead},
+    slice,
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+use anyhow::{anyhow, Context, Result};
+
+use super::{
+    ansi::apply_ansi,
+    columns::Columns,
+    diagnostic::{Diagnostic, Diagnostics},
+    from_reader,
+    header,
+    json::stream_json_lines,
+    lines::LineParser,
+    records,
+    InputFormat,
+    MenuOption,
+};
+
+const SOURCE: &str = "stdin";
+
+pub struct Stream {
+    pub receiver: Receiver<Result<MenuOption<'static, 'static>>>,
+    pub keyed: bool,
+}
+
+pub fn stream_stdin(
+    format: InputFormat,
+    columns: Option<Columns>,
+    read0: bool,
+    ansi: bool,
+) -> Stream {
+    let keyed = columns.is_none() && !matches!(format, InputFormat::Plain);
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let send = |parsed: Result<MenuOption<'static, 'static>, Diagnostic>| {
+            let parsed = parsed.map_err(|diagnostic| anyhow!(diagnostic.summary()));
+            let parsed = parsed.map(|mut option| {
+                if ansi {
+                    apply_ansi(slice::from_mut(&mut option));
+                }
+                option
+            });
+            sender.send(parsed).is_ok()
+        };
+        let reader = stdin().lock();
+        let streamed = match (format, columns) {
+            (_, Some(columns)) => {
+                stream_flat(records(reader, read0), |line| columns.option(&line), send)
+            },
+            (InputFormat::Lines, None) => {
+                LineParser::new(read0).stream(records(reader, read0), SOURCE, send)
+            },
+            (InputFormat::Json, None) => match from_reader(reader, SOURCE, None, format, read0) {
+                Ok(menu) => {
+                    menu.options.into_vec().into_iter().map(Ok).all(send);
+                    Ok(())
+                },
+                Err(error) => error.downcast().map(|Diagnostics(diagnostics)| {
+                    diagnostics.into_iter().map(Err).all(send);
+                }),
+            },
+            (InputFormat::Jsonl, None) => stream_json_lines(records(reader, read0), SOURCE, send),
+            (InputFormat::Plain, None) => {
+                stream_flat(records(reader, read0), MenuOption::plain, send)
+            },
+        };
+        if let Err(error) = streamed {
+            sender.send(Err(error)).ok();
+        }
+    });
+    Stream { receiver, keyed }
+}
+pub fn starts_with_header() -> Result<bool> {
+    let mut stdin = stdin().lock();
+    let buffer = stdin.fill_buf().context("Reading from stdin failed.")?;
+    let first = buffer.split(|&byte| byte == b'\n' || byte == b'\0').next().unwrap_or_default();
+    let complete = first.len() < buffer.len();
+    let first = String::from_utf8_lossy(first).into_owned();
+    drop(stdin);
+
+    let delimiter = header::DELIMITER;
+    Ok(first.trim_end() == delimiter || (!complete && delimiter.starts_with(first.as_str())))
+}
+fn stream_flat(
+    records: impl Iterator<Item = io::Result<String>>,
+    parse: impl Fn(String) -> MenuOption<'static, 'static>,
+    mut send: impl FnMut(Result<MenuOption<'static, 'static>, Diagnostic>) -> bool,
+) -> Result<()> {
+    for line in records {
+        let line = line.context("Reading from stdin failed.")?;
+        if !line.trim().is_empty() && !send(Ok(parse(line))) {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use std::mem;
+
+use super::MenuOption;
+
+type Node = (usize, MenuOption<'static, 'static>, Vec<MenuOption<'static, 'static>>);
+
+#[derive(Default)]
+pub struct TreeBuilder {
+    parents: Vec<Node>,
+    roots: Vec<MenuOption<'static, 'static>>,
+}
+impl TreeBuilder {
+    pub fn push(&mut self, indent: usize, option: MenuOption<'static, 'static>) {
+        while self.parents.last().is_some_and(|(parent_indent, ..)| *parent_indent >= indent) {
+            self.close();
+        }
+        self.parents.push((indent, option, Vec::new()));
+    }
+    pub fn take_roots(&mut self) -> Vec<MenuOption<'static, 'static>> {
+        mem::take(&mut self.roots)
+    }
+    pub fn finish(mut self) -> Vec<MenuOption<'static, 'static>> {
+        while !self.parents.is_empty() {
+            self.close();
+        }
+        self.roots
+    }
+    fn close(&mut self) {
+        if let Some((_, mut option, children)) = self.parents.pop() {
+            option.children = children.into_boxed_slice();
+            match self.parents.last_mut() {
+                Some((_, _, siblings)) => siblings.push(option),
+                None => self.roots.push(option),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(lines: &[(usize, &str)]) -> Vec<MenuOption<'static, 'static>> {
+        let mut tree = TreeBuilder::default();
+        for &(indent, name) in lines {
+            tree.push(indent, format!("{name} : {name}").parse().unwrap());
+        }
+        tree.finish()
+    }
+    fn outline(options: &[MenuOption]) -> String {
+        let outline = |option: &MenuOption| match &*option.children {
+            [] => option.output.to_string(),
+            children => format!("{}({})", option.output, outline(children)),
+        };
+        options.iter().map(outline).collect::<Vec<_>>().join(" ")
+    }
+
+    #[test]
+    fn nests_options_under_less_indented_ones() {
+        let options = build(&[(0, "a"), (2, "b"), (4, "c"), (2, "d"), (0, "e")]);
+        assert_eq!(outline(&options), "a(b(c) d) e");
+    }
+    #[test]
+    fn closes_every_deeper_parent_on_dedent() {
+        let options = build(&[(0, "a"), (4, "b"), (8, "c"), (2, "d"), (1, "e")]);
+        assert_eq!(outline(&options), "a(b(c) d e)");
+    }
+    #[test]
+    fn takes_the_closed_roots_so_far() {
+        let mut tree = TreeBuilder::default();
+        for (indent, name) in [(0, "a"), (2, "b"), (0, "c")] {
+            tree.push(indent, format!("{name} : {name}").parse().unwrap());
+        }
+
+        assert_eq!(outline(&tree.take_roots()), "a(b)");
+        assert!(tree.take_roots().is_empty());
+        assert_eq!(outline(&tree.finish()), "c");
+    }
+}